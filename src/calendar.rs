@@ -2,7 +2,8 @@
 
 use ansi_term::Colour::*;
 use chrono::Utc;
-use ron::de::from_reader;
+use lazy_static::lazy_static;
+use ron::de::{from_reader, from_str};
 use ron::ser::to_string_pretty;
 use serde_derive::{Deserialize, Serialize};
 use std::borrow::Borrow;
@@ -10,11 +11,13 @@ use std::borrow::BorrowMut;
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::hash_map::Keys;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::error::Error;
 use std::fmt;
 use std::io;
+use std::path::Path;
 use std::rc::Rc;
 use std::str::FromStr;
 use strum_macros::{Display, EnumString};
@@ -28,6 +31,13 @@ pub struct Calendar {
     pub info: FileInfo,
     /** the province owning this calendar */
     pub province: Province,
+    /** whether the province keeps the Ember Days and Rogation Days */
+    #[serde(default)]
+    pub days_of_prayer: bool,
+    /** how Easter Day, and so every date that depends on it, is
+    reckoned */
+    #[serde(default)]
+    pub easter: EasterReckoning,
     holydays: Vec<HolydayRef>,
     #[serde(skip)]
     holydays_by_tag: HashMap<String, HolydayRef>,
@@ -38,6 +48,8 @@ impl Default for Calendar {
         Self {
             info: FileInfo::default(),
             province: Province::Unknown,
+            days_of_prayer: false,
+            easter: EasterReckoning::Gregorian,
             holydays: vec![],
             holydays_by_tag: HashMap::new(),
         }
@@ -60,7 +72,7 @@ impl Calendar {
         R: io::Read,
     {
         let mut u: Self = from_reader(reader).map_err(CalendarError::from_error)?;
-        println!(
+        eprintln!(
             "{}",
             Green.paint(format!("reading calendar for {:?}", u.province))
         );
@@ -84,6 +96,17 @@ impl Calendar {
     }
     /** apply [EdMods] to the calendar */
     pub fn apply(&mut self, edits: &EdMods) -> Result<(), CalendarError> {
+        self.apply_edits(edits, None)
+    }
+    /** apply [EdMods] to the calendar as a layer (e.g. of a diocese),
+    recording the layer as the provenance of each field that it sets */
+    pub fn apply_layer(&mut self, edits: &EdMods, layer: &str) -> Result<(), CalendarError> {
+        self.apply_edits(edits, Some(layer))
+    }
+    fn apply_edits(&mut self, edits: &EdMods, layer: Option<&str>) -> Result<(), CalendarError> {
+        if let Some(days_of_prayer) = edits.days_of_prayer {
+            self.days_of_prayer = days_of_prayer;
+        }
         for em in &edits.holydays {
             match self.get_by_tag(&em.tag) {
                 Ok(mut holyday) => {
@@ -91,16 +114,51 @@ impl Calendar {
                         self.delete_by_tag(&em.tag);
                     } else {
                         holyday.modify(em);
+                        if let Some(layer) = layer {
+                            holyday.set_provenance(&em.fields(), layer);
+                        }
                     }
                 }
                 Err(_e) => {
-                    println!("tag {} not found, adding new holy day", &em.tag);
-                    self.add(&em.to_holyday()?);
+                    eprintln!("tag {} not found, adding new holy day", &em.tag);
+                    let mut holyday = em.to_holyday()?;
+                    if let Some(layer) = layer {
+                        holyday.set_provenance(&HOLYDAY_FIELDS, layer);
+                    }
+                    self.add(&holyday);
                 }
             }
         }
         Ok(())
     }
+    /** Read a layered calendar: the base calendar and the overlays of
+    a [CalendarManifest], with file names relative to the manifest. */
+    pub fn read_layered(manifest_filename: &str) -> Result<Self, CalendarError> {
+        let open_path = |path: &Path| {
+            std::fs::File::open(path)
+                .map_err(|e| CalendarError::new(&format!("cannot open {}: {}", path.display(), e)))
+        };
+        let open = |filename: &str| {
+            open_path(
+                &Path::new(manifest_filename)
+                    .parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .join(filename),
+            )
+        };
+        let manifest = CalendarManifest::read(open_path(Path::new(manifest_filename))?)?;
+        let mut cal = Self::read(open(&manifest.base.file)?)?;
+        for r in &cal.holydays {
+            r.r.as_ref()
+                .borrow_mut()
+                .set_provenance(&HOLYDAY_FIELDS, &manifest.base.name);
+        }
+        for overlay in &manifest.overlays {
+            eprintln!("{}", Green.paint(format!("applying {}", overlay.name)));
+            cal.apply_layer(&EdMods::read(open(&overlay.file)?)?, &overlay.name)?;
+        }
+        Ok(cal)
+    }
     /** find the [Holyday] with a specified tag, or `None` */
     pub fn get_by_tag(&mut self, tag: &str) -> Result<HolydayRef, CalendarError> {
         let re = self.holydays_by_tag.get(tag);
@@ -144,6 +202,40 @@ impl Calendar {
         self.holydays.sort_by(HolydayRef::cmp_by_tag);
     }
 }
+/** A [CalendarManifest] describes a layered calendar: a base
+[Calendar] (e.g. of a province) and the [EdMods] overlays (e.g. of a
+diocese, then of a parish) that are applied to it in order, so that
+local variations do not need a copy of the calendar of the province.
+See [Calendar::read_layered]. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct CalendarManifest {
+    #[serde(default)]
+    /** info about the file */
+    pub info: FileInfo,
+    /** the calendar that the overlays are applied to */
+    pub base: Layer,
+    /** the overlays, in the order they are applied */
+    #[serde(default)]
+    pub overlays: Vec<Layer>,
+}
+/** A layer of a [CalendarManifest]. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Layer {
+    /** the name of the layer e.g. "Diocese of Oxford", used as the
+    provenance of the fields it sets */
+    pub name: String,
+    /** the file, relative to the manifest */
+    pub file: String,
+}
+impl CalendarManifest {
+    /** read a manifest from a reader */
+    pub fn read<R>(reader: R) -> Result<Self, CalendarError>
+    where
+        R: io::Read,
+    {
+        from_reader(reader).map_err(CalendarError::from_error)
+    }
+}
 /** Information about a file that can be used e.g. for tracking its origin. */
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct FileInfo {
@@ -197,6 +289,58 @@ impl fmt::Display for Province {
         }
     }
 }
+/** SundayScheme is the way that the Sundays of the year are named in
+a [Province] */
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum SundayScheme {
+    /** Common Worship e.g. "3rd Sunday of Epiphany", "4th Sunday before Advent" */
+    CommonWorship,
+    /** Revised Common Lectionary e.g. "Proper 12" */
+    RevisedCommonLectionary,
+    /** Book of Common Prayer 1662 e.g. "Septuagesima", "16th Sunday after Trinity" */
+    BookOfCommonPrayer,
+}
+impl From<Province> for SundayScheme {
+    fn from(province: Province) -> Self {
+        match province {
+            Province::ECUSA | Province::Canada => SundayScheme::RevisedCommonLectionary,
+            Province::BCP => SundayScheme::BookOfCommonPrayer,
+            _ => SundayScheme::CommonWorship,
+        }
+    }
+}
+/** TransferScheme is the set of rules that a [Province] follows to
+transfer or drop holy days that fall on Sundays, in Holy Week or Easter
+Week, or on the same day as a more important holy day */
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum TransferScheme {
+    /** the Common Worship "Rules to Order the Christian Year" */
+    CommonWorship,
+    /** the "Calendar of the Church Year" of the 1979 Book of Common
+    Prayer: Festivals on any Sunday (other than the Feasts of our Lord
+    outside Advent, Lent and Eastertide) and in Holy Week or Easter Week
+    are transferred, and lesser holy days are not kept on Sundays */
+    CommonPrayer1979,
+    /** the 1662 Book of Common Prayer, which has no rules of
+    transfer: every holy day, red letter or black letter, is kept on its
+    own date, even on a Sunday or in Holy Week or Easter Week. Only the
+    [TransferType::Rules] and [TransferType::Before] of an holy day
+    still apply. */
+    CommonPrayer1662,
+    /** A Prayer Book for Australia: as Common Worship, but
+    Commemorations are not kept on Sundays */
+    Australia,
+}
+impl From<Province> for TransferScheme {
+    fn from(province: Province) -> Self {
+        match province {
+            Province::ECUSA => TransferScheme::CommonPrayer1979,
+            Province::BCP => TransferScheme::CommonPrayer1662,
+            Province::Australia => TransferScheme::Australia,
+            _ => TransferScheme::CommonWorship,
+        }
+    }
+}
 /** Data about a [Province] */
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProvinceData {
@@ -310,16 +454,53 @@ pub struct Holyday {
     pub has_eve: bool,
     /** date calculation */
     pub date_cal: DateCal,
+    /** where the holy day goes if it is on 29 February in a year that
+    is not a leap year */
+    pub leap_day: LeapDayPolicy,
     /** whether and how the holy day must be transferred to another
     date or dropped */
     pub transfer: TransferType,
+    /** the layer that set each field (by name e.g. "date_cal"), if the
+    calendar is layered */
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub provenance: BTreeMap<String, String>,
 }
+/** the names of the fields of an [Holyday] that a layer can set */
+const HOLYDAY_FIELDS: [&str; 12] = [
+    "title",
+    "description",
+    "main",
+    "other",
+    "death",
+    "refs",
+    "class",
+    "tag",
+    "has_eve",
+    "date_cal",
+    "leap_day",
+    "transfer",
+];
 impl Holyday {
+    /** record the layer that set some fields */
+    fn set_provenance(&mut self, fields: &[&str], layer: &str) {
+        for field in fields {
+            self.provenance
+                .insert((*field).to_string(), layer.to_string());
+        }
+    }
+    /** The layer that set a field (by name e.g. "date_cal"), or `None`
+    if the calendar is not layered. */
+    pub fn set_by(&self, field: &str) -> Option<&str> {
+        self.provenance.get(field).map(|layer| layer.as_str())
+    }
     /** modify an Holy Day according to an HolydayMod */
     pub fn modify(&mut self, m: &HolydayMod) {
         if let Some(t) = &m.title {
             self.title = t.to_string();
         }
+        if let Some(d) = &m.description {
+            self.description = d.to_string();
+        }
         if let Some(mn) = &m.main {
             self.main = mn.clone();
         }
@@ -341,6 +522,9 @@ impl Holyday {
         if let Some(c) = &m.date_cal {
             self.date_cal = c.clone();
         }
+        if let Some(l) = &m.leap_day {
+            self.leap_day = *l;
+        }
         if let Some(t) = &m.transfer {
             self.transfer = t.clone();
         }
@@ -381,7 +565,9 @@ impl Default for Holyday {
             tag: "".to_string(),
             has_eve: false,
             date_cal: DateCal::Fixed { month: 1, day: 1 },
+            leap_day: LeapDayPolicy::default(),
             transfer: TransferType::Normal,
+            provenance: BTreeMap::new(),
         }
     }
 }
@@ -437,6 +623,17 @@ impl HolydayRef {
         let hr: &Holyday = &self.r.as_ref().borrow();
         hr.class
     }
+    /** `has_eve` returns whether the holyday has an eve */
+    pub fn has_eve(&self) -> bool {
+        let hr: &Holyday = &self.r.as_ref().borrow();
+        hr.has_eve
+    }
+    /** `leap_day` returns where the holyday goes on 29 February in a
+    year that is not a leap year */
+    pub fn leap_day(&self) -> LeapDayPolicy {
+        let hr: &Holyday = &self.r.as_ref().borrow();
+        hr.leap_day
+    }
     /** `transfer` returns the transfer type of the holyday */
     pub fn transfer(&self) -> TransferType {
         let hr: &Holyday = &self.r.as_ref().borrow();
@@ -466,6 +663,14 @@ impl HolydayRef {
     pub fn modify(&mut self, m: &HolydayMod) {
         self.r.as_ref().borrow_mut().modify(m);
     }
+    fn set_provenance(&mut self, fields: &[&str], layer: &str) {
+        self.r.as_ref().borrow_mut().set_provenance(fields, layer);
+    }
+    /** `set_by` returns the layer that set a field of the holyday, see [Holyday::set_by] */
+    pub fn set_by(&self, field: &str) -> Option<String> {
+        let hr: &Holyday = &self.r.as_ref().borrow();
+        hr.set_by(field).map(String::from)
+    }
 }
 impl fmt::Debug for HolydayRef {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -519,6 +724,10 @@ impl FromStr for HolydaySort {
 pub struct EdMods {
     #[serde(default)]
     info: FileInfo,
+    /** whether the province keeps the Ember Days and Rogation Days,
+    if changed */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days_of_prayer: Option<bool>,
     /** the [HolydayMod]s in this EdMods */
     pub holydays: Vec<HolydayMod>,
 }
@@ -528,9 +737,9 @@ impl EdMods {
     where
         R: io::Read,
     {
-        println!("{}", Green.paint("reading edits"));
+        eprintln!("{}", Green.paint("reading edits"));
         let u: Self = from_reader(reader).map_err(CalendarError::from_error)?;
-        println!(
+        eprintln!(
             "{}",
             Green.paint(format!("modifications read from reader with {:?}", u.info))
         );
@@ -541,6 +750,7 @@ impl From<&mut Calendar> for EdMods {
     fn from(c: &mut Calendar) -> Self {
         Self {
             info: c.info.clone(),
+            days_of_prayer: Some(c.days_of_prayer),
             holydays: c
                 .holydays
                 .iter_mut()
@@ -589,12 +799,36 @@ pub struct HolydayMod {
     /** date calculation */
     #[serde(skip_serializing_if = "Option::is_none")]
     pub date_cal: Option<DateCal>,
+    /** where the holy day goes on 29 February in a year that is not a
+    leap year */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leap_day: Option<LeapDayPolicy>,
     /** whether and how the holy day can be transferred to another date */
     pub transfer: Option<TransferType>,
     /** whether to delete the [Holyday] */
     pub delete: bool,
 }
 impl HolydayMod {
+    /** the names of the fields of the [Holyday] that this changes */
+    fn fields(&self) -> Vec<&'static str> {
+        [
+            ("title", self.title.is_some()),
+            ("description", self.description.is_some()),
+            ("main", self.main.is_some()),
+            ("other", self.other.is_some()),
+            ("death", self.death.is_some()),
+            ("refs", self.refs.is_some()),
+            ("class", self.class.is_some()),
+            ("has_eve", self.has_eve.is_some()),
+            ("date_cal", self.date_cal.is_some()),
+            ("leap_day", self.leap_day.is_some()),
+            ("transfer", self.transfer.is_some()),
+        ]
+        .iter()
+        .filter(|(_, set)| *set)
+        .map(|(field, _)| *field)
+        .collect()
+    }
     /** convert an EdMod to an [Holyday]. All fields must be specified. */
     pub fn to_holyday(&self) -> Result<Holyday, CalendarError> {
         let title = self.clone().title.ok_or_else(|| {
@@ -632,9 +866,11 @@ impl HolydayMod {
                 .ok_or_else(|| {
                     CalendarError::new("adding holy day and field not specified -  date_cal ")
                 })?,
+            leap_day: self.leap_day.unwrap_or_default(),
             transfer: self.transfer.clone().ok_or_else(|| {
                 CalendarError::new("adding holy day and field not specified -  transfer ")
             })?,
+            provenance: BTreeMap::new(),
         };
         Ok(e)
     }
@@ -652,6 +888,7 @@ impl Default for HolydayMod {
             tag: "".to_string(),
             has_eve: None,
             date_cal: None,
+            leap_day: None,
             transfer: None,
             delete: false,
         }
@@ -670,13 +907,14 @@ impl From<Holyday> for HolydayMod {
             tag: e.tag,
             has_eve: Some(e.has_eve),
             date_cal: Some(e.date_cal),
+            leap_day: Some(e.leap_day),
             transfer: Some(e.transfer),
             delete: false,
         }
     }
 }
 fn some_unless_blank(s: &str) -> Option<String> {
-    if s == "" {
+    if s.is_empty() {
         None
     } else {
         Some(s.to_string())
@@ -684,8 +922,9 @@ fn some_unless_blank(s: &str) -> Option<String> {
 }
 
 /** Holy DayClass is the level of the holy day and can be commemoration, lesser
-festival, festival, principal feast, also unclassified and (ordinary) Sunday*/
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+festival, festival, principal feast, also unclassified, (ordinary) Sunday and
+day of prayer (Ember and Rogation Days, which are not festivals)*/
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
 pub enum HolydayClass {
     NotAFestival,
     Unclassified,
@@ -695,6 +934,34 @@ pub enum HolydayClass {
     Sunday,
     CorpusChristi,
     Principal,
+    DayOfPrayer,
+}
+impl HolydayClass {
+    /** the rank of the class, from the lowest; a day of prayer ranks
+    below every festival */
+    fn rank(self) -> u8 {
+        match self {
+            HolydayClass::NotAFestival => 0,
+            HolydayClass::DayOfPrayer => 1,
+            HolydayClass::Unclassified => 2,
+            HolydayClass::Commemoration => 3,
+            HolydayClass::LesserFestival => 4,
+            HolydayClass::Festival => 5,
+            HolydayClass::Sunday => 6,
+            HolydayClass::CorpusChristi => 7,
+            HolydayClass::Principal => 8,
+        }
+    }
+}
+impl Ord for HolydayClass {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+impl PartialOrd for HolydayClass {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 impl fmt::Display for HolydayClass {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -725,6 +992,60 @@ pub enum DateCal {
     /** a date specified by month and day; may be in the previous
     calendar year (depending on the date relative to Advent). */
     Fixed { month: u8, day: u8 },
+    /** Orthodox Easter Sunday (Pascha), calculated using the Julian
+    calendar */
+    OrthodoxEaster,
+    /** the nth specified day of the week in a month (e.g. the fourth
+    Thursday in November); `n` starts from 1 */
+    NthWeekdayOfMonth {
+        month: u8,
+        n: u8,
+        day_of_week: OrderableDayOfWeek,
+    },
+    /** the last specified day of the week in a month (e.g. the last
+    Sunday in January) */
+    LastWeekdayOfMonth {
+        month: u8,
+        day_of_week: OrderableDayOfWeek,
+    },
+    /** a specified day of the week before a specified date (e.g. the
+    Sunday before Advent). The specified date itself is included
+    unless `exclusive` is set. */
+    Previous {
+        date: Box<DateCal>,
+        day_of_week: OrderableDayOfWeek,
+        #[serde(default)]
+        exclusive: bool,
+    },
+    /** the specified day of the week nearest to a specified date, so
+    up to three days before or after it (e.g. the Sunday nearest 5
+    January is the Sunday between 2 and 8 January). The specified date
+    itself is included unless `exclusive` is set, when the day of the
+    week a week later is used instead. */
+    Nearest {
+        date: Box<DateCal>,
+        day_of_week: OrderableDayOfWeek,
+        #[serde(default)]
+        exclusive: bool,
+    },
+    /** a date specified by month and day that is one day later in a
+    leap year if it is from 24 to 28 February, because the 1662 Book
+    of Common Prayer counts 24 February as the bissextile (leap) day
+    (e.g. St Matthias is on 24 February, or 25 February in a leap
+    year) */
+    Bissextile { month: u8, day: u8 },
+}
+/** Where an holy day on 29 February goes in a year that is not a leap
+year */
+#[derive(
+    Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash, Default,
+)]
+pub enum LeapDayPolicy {
+    /** 28 February */
+    #[default]
+    February28,
+    /** 1 March */
+    March1,
 }
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Hash)]
 /** a [chrono::Weekday] with an ordering, so it can be part of a
@@ -757,25 +1078,156 @@ impl From<OrderableDayOfWeek> for chrono::Weekday {
     }
 }
 
+/** EasterReckoning is the method used to calculate the date of Easter */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone, Hash, Default)]
+pub enum EasterReckoning {
+    /** the Gregorian method, used by the Western churches */
+    #[default]
+    Gregorian,
+    /** the Julian method, used by the Orthodox churches; dates are
+    still given in the Gregorian calendar */
+    Julian,
+}
+impl FromStr for EasterReckoning {
+    type Err = CalendarError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gregorian" | "western" => Ok(EasterReckoning::Gregorian),
+            "julian" | "orthodox" => Ok(EasterReckoning::Julian),
+            other => Err(CalendarError::new(&format!(
+                "unknown Easter reckoning {}",
+                other
+            ))),
+        }
+    }
+}
 /** TransferType indicates whether and how an holy day can be transferred
 to another date or dropped */
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub enum TransferType {
     /** follow the usual rules using the holy day's [HolydayClass] */
     Normal,
-    /** special rule for Annunciation  */
+    /** the rules for the Annunciation, see [TransferType::rules] */
     Annunciation,
     //   BaptismOfChrist,
-    /** special rule for Joseph */
+    /** the rules for Joseph, see [TransferType::rules] */
     Joseph,
-    /** special rule for George */
+    /** the rules for George, see [TransferType::rules] */
     George,
-    /** special rule for Mark  */
+    /** the rules for Mark, see [TransferType::rules] */
     Mark,
     /** must occur before the specified date, otherwise drop */
     Before(DateCal),
     /** do not transfer */
     DoNotTransfer,
+    /** follow the [TransferRule]s; the first rule whose conditions
+    all hold is applied, and the holy day is kept on its date if none
+    do */
+    Rules(Vec<TransferRule>),
+}
+/** The rules for the holy days that have their own [TransferType]. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+struct SpecialTransferRules {
+    annunciation: Vec<TransferRule>,
+    joseph: Vec<TransferRule>,
+    george: Vec<TransferRule>,
+    mark: Vec<TransferRule>,
+}
+lazy_static! {
+    /** The rules in `data/transfers/special.transfers`, read once */
+    static ref SPECIAL_TRANSFER_RULES: Result<SpecialTransferRules, CalendarError> =
+        from_str(include_str!("../data/transfers/special.transfers")).map_err(|e| {
+            CalendarError::new(&format!("bad built-in transfer rules: {}", e))
+        });
+}
+impl TransferType {
+    /** The [TransferRule]s of the transfer type. The holy days with
+    their own transfer type (e.g. [TransferType::George]) are aliases
+    for rules in `data/transfers/special.transfers`. Returns `None` for
+    the transfer types that do not use rules, and an error if the
+    built-in rules cannot be read.
+
+    ```
+    use anglican_calendar::calendar::{
+        DateCal, TransferAction, TransferCondition, TransferPeriod, TransferType,
+    };
+    let rules = TransferType::George.rules().unwrap().unwrap();
+    assert_eq!(vec![TransferCondition::In(TransferPeriod::Easter)], rules[0].when);
+    assert_eq!(
+        TransferAction::MoveTo(DateCal::After {
+            date: Box::new(DateCal::Easter),
+            rel: 8
+        }),
+        rules[0].action
+    );
+    assert_eq!(Ok(None), TransferType::Normal.rules());
+    ```
+    */
+    pub fn rules(&self) -> Result<Option<Vec<TransferRule>>, CalendarError> {
+        let special = || SPECIAL_TRANSFER_RULES.as_ref().map_err(|e| e.clone());
+        Ok(match self {
+            TransferType::Annunciation => Some(special()?.annunciation.clone()),
+            TransferType::Joseph => Some(special()?.joseph.clone()),
+            TransferType::George => Some(special()?.george.clone()),
+            TransferType::Mark => Some(special()?.mark.clone()),
+            TransferType::Rules(rules) => Some(rules.clone()),
+            TransferType::Normal | TransferType::Before(_) | TransferType::DoNotTransfer => None,
+        })
+    }
+}
+/** A TransferRule transfers or drops an holy day if all its conditions
+hold e.g. if George is in Holy Week or Easter Week, it is moved to the
+Monday after the 2nd Sunday of Easter. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct TransferRule {
+    /** the conditions, which must all hold; the rule always applies if
+    there are none */
+    #[serde(default)]
+    pub when: Vec<TransferCondition>,
+    /** what to do with the holy day */
+    pub action: TransferAction,
+}
+/** A condition of a [TransferRule], tested on the date of the holy
+day before the rule is applied. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub enum TransferCondition {
+    /** the holy day is on a Sunday */
+    OnSunday,
+    /** the holy day is in the period */
+    In(TransferPeriod),
+    /** another date (e.g. that of another holy day) is in the period */
+    DateIn(DateCal, TransferPeriod),
+    /** the holy day is on the same date as an holy day of this class or
+    higher (Festivals are not displaced by ordinary Sundays) */
+    ClashesWith(HolydayClass),
+}
+/** A period of the year for a [TransferCondition]. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub enum TransferPeriod {
+    /** from Advent Sunday to Christmas Eve */
+    Advent,
+    /** from Ash Wednesday to Pentecost */
+    LentOrEastertide,
+    /** Holy Week or Easter Week i.e. from Palm Sunday to the 2nd Sunday
+    of Easter */
+    Easter,
+    /** from the first date to the second date, both included */
+    Between(DateCal, DateCal),
+}
+/** What a [TransferRule] does to an holy day. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub enum TransferAction {
+    /** keep the holy day on its date, and do not try any more rules */
+    Keep,
+    /** move the holy day to the next day */
+    NextDay,
+    /** move the holy day to the next day that is not a Sunday */
+    NextWeekday,
+    /** move the holy day to a date */
+    MoveTo(DateCal),
+    /** leave the holy day out of the calendar */
+    Drop,
 }
 /** A principal attribute of a [Holyday] (at preseent only [MainAttribute::Martyr])   */
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone, Hash)]
@@ -841,29 +1293,71 @@ pub enum Season {
     Easter,
     Ordinary,
 }
+impl fmt::Display for Season {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+/** Part of a [Season], or a season that is only kept in some
+provinces */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+pub enum SubSeason {
+    /** Palm Sunday to Easter Eve */
+    HolyWeek,
+    /** Easter Day to the 2nd Sunday of Easter */
+    EasterOctave,
+    /** All Saints' Day to the eve of Advent Sunday (Common Worship) */
+    Kingdom,
+    /** Septuagesima to Shrove Tuesday (Book of Common Prayer) */
+    PreLent,
+    /** Trinity Sunday until the next season (Common Worship and Book
+    of Common Prayer) */
+    Trinity,
+}
+impl fmt::Display for SubSeason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubSeason::HolyWeek => write!(f, "Holy Week"),
+            SubSeason::EasterOctave => write!(f, "Easter Octave"),
+            SubSeason::Kingdom => write!(f, "Kingdom"),
+            SubSeason::PreLent => write!(f, "Pre-Lent"),
+            SubSeason::Trinity => write!(f, "Trinity"),
+        }
+    }
+}
 /** the colour for a [Holyday] */
-#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
 pub enum SeasonColour {
     White,
     Red,
     Purple,
     Green,
+    /** rose, for Gaudete (Advent 3) and Laetare (Lent 4) */
+    Rose,
+    /** blue, for Advent */
+    Blue,
+    /** unbleached linen (the Lenten array) */
+    UnbleachedLinen,
 }
 impl SeasonColour {
-    /** colour for HTML */
+    /** colour for HTML, from the CSS3 set of colour names, see
+    [colours](https://www.w3.org/TR/css-color-3) */
     pub fn colour_a(&self) -> String {
         match self {
             SeasonColour::White => "white",
             SeasonColour::Red => "red",
             SeasonColour::Purple => "purple",
             SeasonColour::Green => "green",
+            SeasonColour::Rose => "palevioletred",
+            SeasonColour::Blue => "royalblue",
+            SeasonColour::UnbleachedLinen => "linen",
         }
         .to_string()
     }
     /** colour for HTML */
     pub fn colour_b(&self) -> String {
         match self {
-            SeasonColour::White => "black",
+            SeasonColour::White | SeasonColour::Rose | SeasonColour::UnbleachedLinen => "black",
             _ => "white",
         }
         .to_string()
@@ -875,7 +1369,7 @@ impl fmt::Display for SeasonColour {
     }
 }
 /** A CalendarError is an [Error] which can be used in this crate.  */
-#[derive(Debug, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub struct CalendarError {
     msg: String,
 }
@@ -884,6 +1378,10 @@ impl CalendarError {
     pub fn new(m: &str) -> Self {
         CalendarError { msg: m.to_string() }
     }
+    /** the error text */
+    pub fn message(&self) -> &str {
+        &self.msg
+    }
     /** convert an [Error] of any type to CalendarError */
     pub fn from_error<Err>(err: Err) -> Self
     where
@@ -894,7 +1392,7 @@ impl CalendarError {
         } else {
             "".to_string()
         };
-        println!("{}", Purple.bold().paint(format!("error is {:#?}", err)));
+        eprintln!("{}", Purple.bold().paint(format!("error is {:#?}", err)));
         CalendarError {
             msg: format!("error: {:?}{}", err.source().to_owned(), srce),
         }
@@ -941,7 +1439,7 @@ impl ProvHolydaysByDate {
     }
     /** get the   [ProvHolyday]s for a date */
     pub fn by_date(&self, date: DateCal) -> impl Iterator<Item = &ProvHolyday> {
-        self.phbd.get(&date).unwrap().into_iter()
+        self.phbd.get(&date).unwrap().iter()
     }
 }
 /**  list all [ProvHolyday]s, grouped by tag, for reports */
@@ -972,7 +1470,7 @@ impl ProvHolydaysByTag {
     }
     /**  get the   [ProvHolyday]s for a tag */
     pub fn by_tag(&self, tag: String) -> impl Iterator<Item = &ProvHolyday> {
-        self.phbt.get(&tag).unwrap().into_iter()
+        self.phbt.get(&tag).unwrap().iter()
     }
 }
 /** a [Holyday] in a specific [Calendar] (identified by its [Province]) */
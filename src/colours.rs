@@ -0,0 +1,224 @@
+/*! Implements the rules for the liturgical colours of a province. The
+rules are data, like a [calendar::Calendar], so that provinces can
+keep their own customs. The Common Worship rules are built in. */
+
+use crate::calendar;
+use crate::year_calendar::Year;
+use ansi_term::Colour::*;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use lazy_static::lazy_static;
+use ron::de::{from_reader, from_str};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+/** [ColourRules] give the liturgical colour for each day of the year
+and for each holy day e.g. the Common Worship rules for the Church of
+England. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct ColourRules {
+    #[serde(default)]
+    /** info about the file */
+    pub info: calendar::FileInfo,
+    /** the province whose customs these are */
+    pub province: calendar::Province,
+    /** the optional colours in use, see [ColourRules::enable] */
+    #[serde(default)]
+    pub options: HashSet<ColourOption>,
+    /** the colours of the seasons; the first matching range is used */
+    seasons: Vec<SeasonRule>,
+    /** the colour of the days not in any of the seasons */
+    default: calendar::SeasonColour,
+    /** the colours of the holy days; the first matching rule is used,
+    otherwise the holy day has the colour of the day */
+    feasts: Vec<FeastRule>,
+}
+/** The colour of a range of days. The dates are calculated in the
+calendar year of the day whose colour is wanted, and both are
+included. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct SeasonRule {
+    /** the first day of the range */
+    pub from: calendar::DateCal,
+    /** the last day of the range */
+    pub to: calendar::DateCal,
+    /** the colour */
+    pub colour: calendar::SeasonColour,
+    /** the range is only used if this option is enabled */
+    #[serde(default)]
+    pub option: Option<ColourOption>,
+}
+impl SeasonRule {
+    /** whether the range contains the date; a range whose dates cannot
+    be calculated is not used */
+    fn contains(&self, year: &Year, date: NaiveDate) -> bool {
+        let leap_day = calendar::LeapDayPolicy::default();
+        match (
+            year.date_cal_to_date(&self.from, leap_day),
+            year.date_cal_to_date(&self.to, leap_day),
+        ) {
+            (Ok(from), Ok(to)) => date >= from && date <= to,
+            _ => false,
+        }
+    }
+}
+/** An optional use of colours, which a province or a parish may or may
+not follow. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum ColourOption {
+    /** rose on Gaudete (Advent 3) and Laetare (Lent 4) */
+    Rose,
+    /** blue in Advent */
+    Blue,
+    /** unbleached linen (the Lenten array) in Lent */
+    LentenArray,
+}
+impl FromStr for ColourOption {
+    type Err = calendar::CalendarError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rose" => Ok(ColourOption::Rose),
+            "blue" => Ok(ColourOption::Blue),
+            "lenten-array" | "linen" => Ok(ColourOption::LentenArray),
+            other => Err(calendar::CalendarError::new(&format!(
+                "unknown colour option {}",
+                other
+            ))),
+        }
+    }
+}
+impl fmt::Display for ColourOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+/** The colour of the holy days that match all the specified conditions.
+Empty conditions match every holy day. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct FeastRule {
+    /** the tags of the holy days */
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /** the classes of the holy days */
+    #[serde(default)]
+    pub classes: Vec<calendar::HolydayClass>,
+    /** whether the holy days are of martyrs */
+    #[serde(default)]
+    pub martyr: Option<bool>,
+    /** the colour */
+    pub colour: calendar::SeasonColour,
+}
+impl FeastRule {
+    fn matches(&self, holyday: &calendar::HolydayRef) -> bool {
+        (self.tags.is_empty() || self.tags.contains(&holyday.tag()))
+            && (self.classes.is_empty() || self.classes.contains(&holyday.class()))
+            && match self.martyr {
+                Some(martyr) => martyr == holyday.main().contains(&calendar::MainAttribute::Martyr),
+                None => true,
+            }
+    }
+}
+lazy_static! {
+    /** The rules in `data/colours/cofe.colours`, read once */
+    static ref COMMON_WORSHIP: Result<ColourRules, calendar::CalendarError> =
+        from_str(include_str!("../data/colours/cofe.colours")).map_err(|e| {
+            calendar::CalendarError::new(&format!("bad built-in colour rules: {}", e))
+        });
+}
+impl ColourRules {
+    /** The Common Worship rules, see [the
+    rules](https://www.churchofengland.org/prayer-and-worship/worship-texts-and-resources/common-worship/churchs-year/rules).
+    Returns an error if the built-in rules cannot be read. */
+    pub fn common_worship() -> Result<Self, calendar::CalendarError> {
+        COMMON_WORSHIP.clone()
+    }
+    /** read [ColourRules] from a reader */
+    pub fn read<R>(reader: R) -> Result<Self, calendar::CalendarError>
+    where
+        R: io::Read,
+    {
+        let u: Self = from_reader(reader).map_err(calendar::CalendarError::from_error)?;
+        eprintln!(
+            "{}",
+            Green.paint(format!("reading colours for {:?}", u.province))
+        );
+        Ok(u)
+    }
+    /** Use an optional colour e.g. for a parish that uses blue in
+    Advent although the province does not. */
+    pub fn enable(&mut self, option: ColourOption) {
+        self.options.insert(option);
+    }
+    /** The colour of a day, from the season.
+
+    ```
+    use anglican_calendar::calendar::SeasonColour;
+    use anglican_calendar::colours::{ColourOption, ColourRules};
+    use anglican_calendar::year_calendar::Year;
+    use chrono::NaiveDate;
+    let rules = ColourRules::common_worship().unwrap();
+    let year = Year::new(2021);
+    let colour = |m, d| rules.season_colour(&year, NaiveDate::from_ymd(2021, m, d));
+    assert_eq!(SeasonColour::Purple, colour(3, 1));
+    assert_eq!(SeasonColour::Red, colour(3, 28)); // Palm Sunday
+    assert_eq!(SeasonColour::Red, colour(5, 23)); // Pentecost
+    assert_eq!(SeasonColour::White, colour(5, 30)); // Trinity Sunday
+    assert_eq!(SeasonColour::Green, colour(6, 6));
+    assert_eq!(SeasonColour::Purple, colour(3, 14)); // Laetare
+    let mut rules = rules.clone();
+    rules.enable(ColourOption::Rose);
+    assert_eq!(
+        SeasonColour::Rose,
+        rules.season_colour(&year, NaiveDate::from_ymd(2021, 3, 14))
+    );
+    ```
+    */
+    pub fn season_colour(&self, year: &Year, date: NaiveDate) -> calendar::SeasonColour {
+        let year = if year.ad == date.year() {
+            year.clone()
+        } else {
+            year.other(date.year())
+        };
+        self.seasons
+            .iter()
+            .filter(|s| match s.option {
+                Some(option) => self.options.contains(&option),
+                None => true,
+            })
+            .find(|s| s.contains(&year, date))
+            .map_or(self.default, |s| s.colour)
+    }
+    /** The colour of a holy day kept on a date: the colour of the
+    first matching feast rule, otherwise the colour of the day. */
+    pub fn holyday_colour(
+        &self,
+        holyday: &calendar::HolydayRef,
+        year: &Year,
+        date: NaiveDate,
+    ) -> calendar::SeasonColour {
+        match self.feasts.iter().find(|f| f.matches(holyday)) {
+            Some(f) => f.colour,
+            None => self.season_colour(year, date),
+        }
+    }
+}
+/*
+
+Copyright ©2019 Martin Ellison.  This program is free software: you
+can redistribute it and/or modify it under the terms of the GNU
+General Public License as published by the Free Software Foundation,
+either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but
+WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see
+[licenses](https://www.gnu.org/licenses/). */
@@ -1,6 +1,9 @@
 /*! Libraries for creating iCal calendars for the calendars (lists of
 holy days) of the national churches of the Anglican Communion. */
 pub mod calendar;
+pub mod colours;
+pub mod parish;
+pub mod readings;
 pub mod year_calendar;
 
 /*
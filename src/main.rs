@@ -16,46 +16,222 @@ This program is free software: you can redistribute it and/or modify
 */
 extern crate structopt;
 use anglican_calendar::calendar;
+use anglican_calendar::colours;
+use anglican_calendar::parish;
+use anglican_calendar::readings;
 use anglican_calendar::year_calendar;
 use ansi_term::Colour::*;
+use chrono::Datelike;
 use std::fs::File;
 use std::io::Write;
 use std::io::{BufReader, BufWriter};
+use std::ops::RangeInclusive;
+use std::path::Path;
 use structopt::StructOpt;
 
 fn main() {
-    println!("Copyright ©2019 Martin Ellison. This program comes with ABSOLUTELY NO WARRANTY. This is free software, and you are welcome to redistribute it under the GPL3 licence; see the README file for details.");
+    eprintln!("Copyright ©2019 Martin Ellison. This program comes with ABSOLUTELY NO WARRANTY. This is free software, and you are welcome to redistribute it under the GPL3 licence; see the README file for details.");
     color_backtrace::install();
     if let Err(e) = run() {
-        println!("failed because {:#?}", e);
+        eprintln!("failed because {:#?}", e);
         panic!("failed");
     }
-    println!("{}", Green.paint("done"));
+    eprintln!("{}", Green.paint("done"));
 }
 fn run() -> Result<(), calendar::CalendarError> {
     let opt = Opt::from_args();
-    println!(
-        "{}",
-        Green.paint(format!("reading calendar {}", opt.calendar_filename))
-    );
-    let inf = File::open(opt.calendar_filename).map_err(calendar::CalendarError::from_error)?;
-    let mut br = BufReader::new(inf);
-    let cal = calendar::Calendar::read(&mut br)?;
-    let year_cal = year_calendar::YearCalendar::from_calendar(&cal, opt.year, opt.verbose)?;
+    let mut cal = match (&opt.calendar_filename, &opt.manifest_filename) {
+        (_, Some(manifest_fn)) => {
+            eprintln!(
+                "{}",
+                Green.paint(format!("reading layered calendar {}", manifest_fn))
+            );
+            calendar::Calendar::read_layered(manifest_fn)?
+        }
+        (Some(calendar_fn), None) => {
+            eprintln!(
+                "{}",
+                Green.paint(format!("reading calendar {}", calendar_fn))
+            );
+            let inf = File::open(calendar_fn).map_err(calendar::CalendarError::from_error)?;
+            calendar::Calendar::read(BufReader::new(inf))?
+        }
+        (None, None) => {
+            return Err(calendar::CalendarError::new(
+                "specify the calendar (--calendar) or a manifest (--manifest)",
+            ))
+        }
+    };
+    if let Some(parish_fn) = &opt.parish_filename {
+        let inf = File::open(parish_fn).map_err(calendar::CalendarError::from_error)?;
+        parish::Parish::read(BufReader::new(inf))?.apply(&mut cal);
+    }
+    if let Some(easter) = opt.easter {
+        cal.easter = easter;
+    }
+    let when = match &opt.when {
+        Some(tag) => Some(cal.get_by_tag(tag)?),
+        None => None,
+    };
+    let why = match &opt.why {
+        Some(tag) => Some(cal.get_by_tag(tag)?),
+        None => None,
+    };
+    let mut practices = year_calendar::CommonWorshipOptions::default();
+    if let Some(practices_fn) = &opt.practices_filename {
+        eprintln!(
+            "{}",
+            Green.paint(format!("reading practices {}", practices_fn))
+        );
+        let inf = File::open(practices_fn).map_err(calendar::CalendarError::from_error)?;
+        practices = year_calendar::CommonWorshipOptions::read(BufReader::new(inf))?;
+    }
+    for practice in &opt.practices {
+        practices.enable(*practice);
+    }
+    let options = year_calendar::YearCalendarOptions {
+        church_year: opt.church_year,
+        sundays: opt.sundays,
+        eves: opt.eves,
+        practices,
+    };
+    let years = match (&opt.years, opt.year, opt.on) {
+        (Some(years), _, _) => years.clone(),
+        (None, Some(year), _) => year..=year,
+        (None, None, Some(on)) => {
+            let year = if opt.church_year && on >= year_calendar::Year::new(on.year()).advent_next {
+                on.year() + 1
+            } else {
+                on.year()
+            };
+            year..=year
+        }
+        (None, None, None) => {
+            return Err(calendar::CalendarError::new(
+                "specify the year (--year) or years (--years)",
+            ))
+        }
+    };
+    let mut year_cals =
+        year_calendar::CalendarRange::from_calendar(&cal, years.clone(), &options, opt.verbose)?;
+    let mut colour_rules = colours::ColourRules::common_worship()?;
+    /* the colour rules of the province are used if there are any */
+    let colours_filename = opt.colours_filename.or_else(|| {
+        let provinces = calendar::ProvinceList::make();
+        let colours_fn = format!(
+            "data/colours/{}.colours",
+            provinces.all().get(&cal.province)?.abbrev
+        );
+        if Path::new(&colours_fn).exists() {
+            Some(colours_fn)
+        } else {
+            None
+        }
+    });
+    if let Some(colours_fn) = colours_filename {
+        eprintln!(
+            "{}",
+            Green.paint(format!("reading colour rules {}", colours_fn))
+        );
+        let inf = File::open(colours_fn).map_err(calendar::CalendarError::from_error)?;
+        colour_rules = colours::ColourRules::read(BufReader::new(inf))?;
+    }
+    for option in opt.colour_options {
+        colour_rules.enable(option);
+    }
+    year_cals.set_colour_rules(colour_rules);
+    if let Some(readings_fn) = opt.readings_filename {
+        eprintln!(
+            "{}",
+            Green.paint(format!("reading lectionary {}", readings_fn))
+        );
+        let inf = File::open(readings_fn).map_err(calendar::CalendarError::from_error)?;
+        let readings = readings::Readings::read(BufReader::new(inf))?;
+        let missing = year_cals.add_readings(&readings);
+        if !missing.is_empty() {
+            eprintln!(
+                "{}",
+                Red.paint(format!(
+                    "no readings for principal feasts: {}",
+                    missing.join(", ")
+                ))
+            );
+        }
+    }
+    for e in year_cals.errors() {
+        eprintln!("{}", Red.paint(format!("left out: {}", e.message())));
+    }
     if opt.verbose {
-        println!("{}", Green.paint("year calendar"));
-        println!("{:#?}", year_cal);
-    }
-    println!("{}", Green.paint("generating year calendar"));
-    let ident = format!(
-        "{}-{}",
-        opt.unique,
-        //    chrono::Utc::now().format("%+"),
-        opt.year
-    );
-    let (ical, ical_del) = year_cal.to_ical(ident.as_str());
+        eprintln!("{}", Green.paint("year calendar"));
+        eprintln!("{:#?}", year_cals);
+    }
+    if let Some(on) = opt.on {
+        let yee: Vec<_> = year_cals
+            .calendars()
+            .iter()
+            .flat_map(|yc| yc.on(on).into_iter().map(move |ye| yc.describe(ye)))
+            .collect();
+        println!("{}", on.format("%A %-d %B %Y"));
+        if yee.is_empty() {
+            println!("no holy days");
+        }
+        for ye in yee {
+            println!("{}", ye);
+        }
+    }
+    if let Some(holyday) = when {
+        println!("{}", holyday.title());
+        for yc in year_cals.calendars() {
+            let yee = yc.find_by_tag(&holyday.tag());
+            let dropped = yc.dropped_by_tag(&holyday.tag());
+            if yee.is_empty() && dropped.is_empty() {
+                println!("{}: not in the calendar", yc.title());
+            }
+            for ye in yee {
+                print!("{}: {}", yc.title(), ye.date().format("%A %-d %B %Y"));
+                if ye.is_transferred() {
+                    print!(
+                        ", transferred from {}",
+                        ye.nominal_date().format("%A %-d %B")
+                    );
+                    if let Some(reason) = ye.transfer_reason() {
+                        print!(" ({})", reason);
+                    }
+                }
+                println!();
+            }
+            for (ye, reason) in dropped {
+                println!(
+                    "{}: not kept on {} ({})",
+                    yc.title(),
+                    ye.date().format("%A %-d %B %Y"),
+                    reason
+                );
+            }
+        }
+    }
+    if let Some(holyday) = why {
+        println!("{}", holyday.title());
+        for yc in year_cals.calendars() {
+            let decisions = yc.why(&holyday.tag());
+            if decisions.is_empty() {
+                println!("{}: not in the calendar", yc.title());
+            }
+            for d in decisions {
+                println!("{}: {}", yc.title(), d);
+            }
+        }
+    }
+    if opt.ical_filename.is_none() && opt.ical_del_filename.is_none() {
+        return write_report(&opt.report_filename, &year_cals);
+    }
+    let unique = opt.unique.ok_or_else(|| {
+        calendar::CalendarError::new("specify a unique identifier (--unique) for an iCal file")
+    })?;
+    eprintln!("{}", Green.paint("generating year calendar"));
+    let (ical, ical_del) = year_cals.to_ical(&unique);
     if let Some(ical_fn) = opt.ical_filename {
-        println!(
+        eprintln!(
             "{}",
             Green.paint(format!("writing year calendar {}", ical_fn))
         );
@@ -73,14 +249,21 @@ fn run() -> Result<(), calendar::CalendarError> {
             .map_err(calendar::CalendarError::from_error)?;
         bw.flush().map_err(calendar::CalendarError::from_error)?;
     }
-    if let Some(report_fn) = opt.report_filename {
-        println!(
+    write_report(&opt.report_filename, &year_cals)
+}
+/// Write the report if a report file was specified
+fn write_report(
+    report_filename: &Option<String>,
+    year_cals: &year_calendar::CalendarRange,
+) -> Result<(), calendar::CalendarError> {
+    if let Some(report_fn) = report_filename {
+        eprintln!(
             "{}",
             Green.paint(format!("writing year calendar report {}", report_fn))
         );
         let of = File::create(report_fn).map_err(calendar::CalendarError::from_error)?;
         let mut bw = BufWriter::new(of);
-        year_cal.write_report(&mut bw)?;
+        year_cals.write_report(&mut bw)?;
         bw.flush().map_err(calendar::CalendarError::from_error)?;
     }
     Ok(())
@@ -97,10 +280,46 @@ pub struct Opt {
     verbose: bool,
     /// Year
     #[structopt(short = "y", long = "year")]
-    year: i32,
+    year: Option<i32>,
+    /// Range of years e.g. 2021-2030, for a single iCal and report with UIDs that do not change
+    #[structopt(long = "years", parse(try_from_str = parse_years))]
+    years: Option<RangeInclusive<i32>>,
+    /// Generate the Church year ending in the year (from Advent Sunday in the previous year)
+    #[structopt(short = "l", long = "church-year")]
+    church_year: bool,
+    /// Include the named Sundays of the year (e.g. 3rd Sunday of Epiphany)
+    #[structopt(short = "s", long = "sundays")]
+    sundays: bool,
+    /// Include the eves of holy days that have them
+    #[structopt(short = "e", long = "eves")]
+    eves: bool,
     /// Calendar file to use
     #[structopt(short = "c", long = "calendar")]
-    calendar_filename: String,
+    calendar_filename: Option<String>,
+    /// Manifest of a layered calendar to use instead of a calendar file: a base calendar and overlays of edits
+    #[structopt(short = "m", long = "manifest")]
+    manifest_filename: Option<String>,
+    /// Reckoning of Easter: gregorian or julian (the Orthodox Pascha), instead of the one in the calendar
+    #[structopt(long = "easter")]
+    easter: Option<calendar::EasterReckoning>,
+    /// Parish file with the patronal and dedication festivals of a local church
+    #[structopt(short = "a", long = "parish")]
+    parish_filename: Option<String>,
+    /// Colour rules file (default: data/colours/<province>.colours if there is one, otherwise Common Worship)
+    #[structopt(short = "o", long = "colours")]
+    colours_filename: Option<String>,
+    /// Optional colours to use: rose, blue or lenten-array (may be repeated)
+    #[structopt(short = "k", long = "colour-option")]
+    colour_options: Vec<colours::ColourOption>,
+    /// Optional practices of Common Worship file e.g. (epiphany_on_sunday: true)
+    #[structopt(short = "P", long = "practices")]
+    practices_filename: Option<String>,
+    /// Optional practices of Common Worship to follow: epiphany-on-sunday, presentation-on-sunday, all-saints-on-sunday, matthias-in-february, thomas-in-december or corpus-christi-as-festival (may be repeated)
+    #[structopt(short = "p", long = "practice")]
+    practices: Vec<year_calendar::Practice>,
+    /// Lectionary file with the readings for the holy days and Sundays
+    #[structopt(short = "R", long = "readings")]
+    readings_filename: Option<String>,
     /// iCal output file
     #[structopt(short = "i", long = "ical")]
     ical_filename: Option<String>,
@@ -110,7 +329,29 @@ pub struct Opt {
     /// iCal output file for deletion (apparently does not work)
     #[structopt(short = "d", long = "delical")]
     ical_del_filename: Option<String>,
-    /// unique identifier for calendar **do not use** domain name or email address
+    /// unique identifier for calendar **do not use** domain name or email address (needed for iCal output)
     #[structopt(short = "u", long = "unique")]
-    unique: String,
+    unique: Option<String>,
+    /// Print the holy days kept on a date e.g. 2021-03-25 (the year defaults to the year of the date)
+    #[structopt(long = "on")]
+    on: Option<chrono::NaiveDate>,
+    /// Print the date on which the holy day with the tag is kept, after any transfer, in each year
+    #[structopt(long = "when")]
+    when: Option<String>,
+    /// Print why the holy day with the tag is kept, moved or dropped on each date, in each year
+    #[structopt(long = "why")]
+    why: Option<String>,
+}
+/// Parse a range of years e.g. "2021-2030", or a single year
+fn parse_years(s: &str) -> Result<RangeInclusive<i32>, calendar::CalendarError> {
+    let bad_years = |_| calendar::CalendarError::new(&format!("bad years {}", s));
+    match s.split_once('-') {
+        Some((first, last)) => Ok(
+            first.trim().parse().map_err(bad_years)?..=last.trim().parse().map_err(bad_years)?
+        ),
+        None => {
+            let year = s.trim().parse().map_err(bad_years)?;
+            Ok(year..=year)
+        }
+    }
 }
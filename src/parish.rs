@@ -0,0 +1,224 @@
+/*! Implements the holy days of a parish: its patronal festival and
+its dedication festival, which are added to the [calendar::Calendar]
+of its province. */
+
+use crate::calendar;
+use ansi_term::Colour::*;
+use ron::de::from_reader;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io;
+
+/** A [Parish] is a local church, with the festivals that it keeps in
+addition to the calendar of its province e.g. St Mary's, with its
+patronal festival on 15 August and its dedication festival on the
+first Sunday in October. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Parish {
+    #[serde(default)]
+    /** info about the file */
+    pub info: calendar::FileInfo,
+    /** the name of the parish e.g. "St Mary the Virgin, Anytown" */
+    pub name: String,
+    /** the festival of the saint or mystery to which the church is
+    dedicated */
+    #[serde(default)]
+    pub patronal: Option<ParishFestival>,
+    /** the anniversary of the dedication (consecration) of the church */
+    #[serde(default)]
+    pub dedication: Option<ParishFestival>,
+}
+/** A festival of a [Parish]. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct ParishFestival {
+    /** the name of the festival e.g. "St Mary the Virgin" */
+    pub title: String,
+    /** the date, or a rule for the date e.g. the first Sunday in
+    October if the date of the dedication is not known */
+    pub date_cal: calendar::DateCal,
+    /** how the festival is kept */
+    #[serde(default)]
+    pub keep: ParishKeeping,
+    /** whether the patron is a martyr, for the liturgical colour */
+    #[serde(default)]
+    pub martyr: bool,
+    /** the tag of the holy day in the calendar of the province that
+    the festival takes the place of e.g. "michael all angels" */
+    #[serde(default)]
+    pub replaces: Option<String>,
+}
+/** How a [ParishFestival] is kept. Common Worship allows the patronal
+and dedication festivals to be kept as Festivals or as Principal
+Feasts, and a Principal Feast may be moved to the nearest Sunday. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub enum ParishKeeping {
+    /** as a Festival on its date */
+    #[default]
+    Festival,
+    /** as a Principal Feast on its date */
+    Principal,
+    /** as a Principal Feast on the nearest Sunday, unless that Sunday
+    is a Principal Feast, Advent Sunday, the Baptism of Christ, the
+    1st or 5th Sunday of Lent or Palm Sunday, when it is kept on its
+    date */
+    Sunday,
+}
+impl Parish {
+    /** read a [Parish] from a reader */
+    pub fn read<R>(reader: R) -> Result<Self, calendar::CalendarError>
+    where
+        R: io::Read,
+    {
+        let u: Self = from_reader(reader).map_err(calendar::CalendarError::from_error)?;
+        eprintln!("{}", Green.paint(format!("reading parish {}", u.name)));
+        Ok(u)
+    }
+    /** Add the festivals of the parish to a [calendar::Calendar], with
+    the tags "patronal festival" and "dedication festival". They
+    replace any holy days with those tags, and the holy days that they
+    take the place of.
+
+    ```
+    use anglican_calendar::calendar::{Calendar, HolydayClass};
+    use anglican_calendar::parish::Parish;
+    let parish = Parish::read(
+        r#"(
+            name: "St Michael and All Angels",
+            patronal: Some((
+                title: "St Michael and All Angels",
+                date_cal: Fixed(month: 9, day: 29),
+                keep: Sunday,
+                replaces: Some("michael all angels"),
+            )),
+            dedication: Some((
+                title: "Dedication Festival",
+                date_cal: NthWeekdayOfMonth(month: 10, n: 1, day_of_week: (wd: "Sunday")),
+            )),
+        )"#
+        .as_bytes(),
+    )
+    .unwrap();
+    let mut cal = Calendar::new();
+    parish.apply(&mut cal);
+    let patronal = cal.get_by_tag("patronal festival").unwrap();
+    assert_eq!(HolydayClass::Principal, patronal.class());
+    let dedication = cal.get_by_tag("dedication festival").unwrap();
+    assert_eq!(HolydayClass::Festival, dedication.class());
+    ```
+    */
+    pub fn apply(&self, calendar: &mut calendar::Calendar) {
+        for (tag, festival) in [
+            ("patronal festival", &self.patronal),
+            ("dedication festival", &self.dedication),
+        ]
+        .iter()
+        {
+            if let Some(festival) = festival {
+                calendar.delete_by_tag(tag);
+                if let Some(replaces) = &festival.replaces {
+                    calendar.delete_by_tag(replaces);
+                }
+                calendar.add(&festival.to_holyday(tag));
+            }
+        }
+    }
+}
+impl ParishFestival {
+    /** the [calendar::Holyday] for the festival */
+    fn to_holyday(&self, tag: &str) -> calendar::Holyday {
+        let (class, date_cal, transfer) = match self.keep {
+            ParishKeeping::Festival => (
+                calendar::HolydayClass::Festival,
+                self.date_cal.clone(),
+                calendar::TransferType::Normal,
+            ),
+            ParishKeeping::Principal => (
+                calendar::HolydayClass::Principal,
+                self.date_cal.clone(),
+                calendar::TransferType::Normal,
+            ),
+            ParishKeeping::Sunday => (
+                calendar::HolydayClass::Principal,
+                calendar::DateCal::Nearest {
+                    date: Box::new(self.date_cal.clone()),
+                    day_of_week: calendar::OrderableDayOfWeek::from(chrono::Weekday::Sun),
+                    exclusive: false,
+                },
+                calendar::TransferType::Rules(self.sunday_rules()),
+            ),
+        };
+        calendar::Holyday {
+            title: self.title.clone(),
+            description: self.title.clone(),
+            class,
+            tag: tag.to_string(),
+            main: if self.martyr {
+                vec![calendar::MainAttribute::Martyr].into_iter().collect()
+            } else {
+                HashSet::new()
+            },
+            has_eve: true,
+            date_cal,
+            transfer,
+            ..calendar::Holyday::default()
+        }
+    }
+    /** the rules that keep the festival on its date if the nearest
+    Sunday cannot be used */
+    fn sunday_rules(&self) -> Vec<calendar::TransferRule> {
+        let easter = |rel| calendar::DateCal::After {
+            date: Box::new(calendar::DateCal::Easter),
+            rel,
+        };
+        let sundays = vec![
+            calendar::DateCal::Advent,
+            calendar::DateCal::AdventNext,
+            /* the Baptism of Christ */
+            calendar::DateCal::Next {
+                date: Box::new(calendar::DateCal::Fixed { month: 1, day: 7 }),
+                day_of_week: calendar::OrderableDayOfWeek::from(chrono::Weekday::Sun),
+            },
+            /* the 1st and 5th Sundays of Lent and Palm Sunday */
+            easter(-42),
+            easter(-14),
+            easter(-7),
+        ];
+        let on_date = calendar::TransferAction::MoveTo(self.date_cal.clone());
+        let mut rules = vec![calendar::TransferRule {
+            when: vec![
+                calendar::TransferCondition::OnSunday,
+                calendar::TransferCondition::ClashesWith(calendar::HolydayClass::Principal),
+            ],
+            action: on_date.clone(),
+        }];
+        for sunday in sundays {
+            rules.push(calendar::TransferRule {
+                when: vec![
+                    calendar::TransferCondition::OnSunday,
+                    calendar::TransferCondition::In(calendar::TransferPeriod::Between(
+                        sunday.clone(),
+                        sunday,
+                    )),
+                ],
+                action: on_date.clone(),
+            });
+        }
+        rules
+    }
+}
+/*
+
+Copyright ©2019 Martin Ellison.  This program is free software: you
+can redistribute it and/or modify it under the terms of the GNU
+General Public License as published by the Free Software Foundation,
+either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but
+WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see
+[licenses](https://www.gnu.org/licenses/). */
@@ -0,0 +1,106 @@
+/*! Implements the lectionary data: the Scripture readings for the holy
+days and Sundays of a [calendar::Calendar]. Like a calendar, this is
+not specific to a year, but the readings for a Sunday may depend on
+the lectionary year ([SundayCycle]). */
+
+use crate::calendar;
+use crate::year_calendar::SundayCycle;
+use ansi_term::Colour::*;
+use ron::de::from_reader;
+use serde_derive::{Deserialize, Serialize};
+use std::io;
+
+/** The [Readings] are the Scripture readings for the holy days and
+Sundays of a province e.g. the Common Worship Principal Service
+lectionary for the Church of England. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Readings {
+    #[serde(default)]
+    /** info about the file */
+    pub info: calendar::FileInfo,
+    /** the province whose lectionary this is */
+    pub province: calendar::Province,
+    readings: Vec<Reading>,
+}
+/** The readings for one holy day or Sunday, optionally only in one
+year of the Sunday cycle. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Reading {
+    /** the tag of the holy day e.g. "christmas", or the name of the
+    Sunday in lower case e.g. "3rd sunday of lent" */
+    pub tag: String,
+    /** the year of the Sunday cycle, or `None` if the readings are the
+    same in every year */
+    #[serde(default)]
+    pub year: Option<SundayCycle>,
+    /** the readings e.g. "Isaiah 9.2-7", "Psalm 96" */
+    pub readings: Vec<String>,
+}
+impl Readings {
+    /** read [Readings] from a reader */
+    pub fn read<R>(reader: R) -> Result<Self, calendar::CalendarError>
+    where
+        R: io::Read,
+    {
+        let u: Self = from_reader(reader).map_err(calendar::CalendarError::from_error)?;
+        eprintln!(
+            "{}",
+            Green.paint(format!("reading lectionary for {:?}", u.province))
+        );
+        Ok(u)
+    }
+    /** The readings for a tag in a year of the Sunday cycle. Readings
+    for the specific year are used in preference to readings for every
+    year.
+
+    ```
+    use anglican_calendar::readings::Readings;
+    use anglican_calendar::year_calendar::SundayCycle;
+    let readings = Readings::read(
+        r#"(
+            province: ChurchOfEngland,
+            readings: [
+                (tag: "christmas", readings: ["Isaiah 9.2-7", "Luke 2.1-14"]),
+                (tag: "easter", year: Some(A), readings: ["Matthew 28.1-10"]),
+                (tag: "easter", year: Some(B), readings: ["Mark 16.1-8"]),
+            ],
+        )"#
+        .as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(
+        Some(&vec!["Mark 16.1-8".to_string()]),
+        readings.find("easter", SundayCycle::B)
+    );
+    assert_eq!(None, readings.find("easter", SundayCycle::C));
+    assert_eq!(2, readings.find("christmas", SundayCycle::C).unwrap().len());
+    ```
+    */
+    pub fn find(&self, tag: &str, year: SundayCycle) -> Option<&Vec<String>> {
+        let mut found = None;
+        for r in self.readings.iter().filter(|r| r.tag == tag) {
+            match r.year {
+                Some(y) if y == year => return Some(&r.readings),
+                None => found = Some(&r.readings),
+                _ => {}
+            }
+        }
+        found
+    }
+}
+/*
+
+Copyright ©2019 Martin Ellison.  This program is free software: you
+can redistribute it and/or modify it under the terms of the GNU
+General Public License as published by the Free Software Foundation,
+either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but
+WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see
+[licenses](https://www.gnu.org/licenses/). */
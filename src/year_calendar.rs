@@ -1,6 +1,8 @@
 /*! Implements a calendar for a specific year, as derived from a [calendar::Calendar] */
 extern crate askama;
 use crate::calendar;
+use crate::colours;
+use crate::readings;
 use ansi_term::Color::Yellow;
 use ansi_term::Colour::*;
 use askama::Template;
@@ -10,9 +12,13 @@ use chrono::Duration;
 use chrono::NaiveDate;
 use chrono::Utc;
 use icalendar::*;
+use serde_derive::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
 use std::io::Write;
+use std::ops::RangeInclusive;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 /** A YearCalendar is a calendar for a specific year for a specific
@@ -20,36 +26,685 @@ church e.g. the 2019 calendar of the Anglican Church of Hong Kong. */
 pub struct YearCalendar {
     province: calendar::Province,
     year: Year,
+    church_year: bool,
     holydays_by_date: HashMap<NaiveDate, Vec<YearHolyday>>,
+    /** whether readings have been added with [YearCalendar::add_readings] */
+    has_readings: bool,
+    /** the rules for the liturgical colours */
+    colours: colours::ColourRules,
+    /** the holy days whose dates could not be calculated */
+    errors: Vec<calendar::CalendarError>,
+    /** the holy days that are not kept this year, and why */
+    dropped: Vec<(YearHolyday, DropReason)>,
+    /** why each holy day was kept, moved or dropped, in the order
+    they were decided */
+    decisions: Vec<Decision>,
+}
+/** Options for generating a [YearCalendar]. */
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct YearCalendarOptions {
+    /** generate the Church year, from Advent Sunday in the previous
+    calendar year to the eve of Advent Sunday in this calendar year,
+    instead of the calendar year */
+    pub church_year: bool,
+    /** generate the named Sundays of the year (e.g. "3rd Sunday of
+    Epiphany") using the [calendar::SundayScheme] of the province */
+    pub sundays: bool,
+    /** generate the eves (First Evensong) of the holy days that have
+    them */
+    pub eves: bool,
+    /** the optional practices of Common Worship that are followed */
+    pub practices: CommonWorshipOptions,
+}
+/** The optional practices that Common Worship allows, e.g. for a
+parish. They can be read from a file such as
+`(epiphany_on_sunday: true, thomas_in_december: true)`. Each moves an
+holy day only if the calendar has it on its usual Common Worship date,
+so calendars that already keep another date are not changed. */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(default)]
+pub struct CommonWorshipOptions {
+    /** keep the Epiphany on the Sunday between 2 and 8 January */
+    pub epiphany_on_sunday: bool,
+    /** keep the Presentation of Christ in the Temple (Candlemas) on
+    the Sunday between 28 January and 3 February */
+    pub presentation_on_sunday: bool,
+    /** keep All Saints' Day on the Sunday between 30 October and 5
+    November */
+    pub all_saints_on_sunday: bool,
+    /** keep St Matthias on 24 February instead of 14 May */
+    pub matthias_in_february: bool,
+    /** keep St Thomas on 21 December instead of 3 July */
+    pub thomas_in_december: bool,
+    /** keep the Day of Thanksgiving for Holy Communion (Corpus
+    Christi) as a Festival */
+    pub corpus_christi_as_festival: bool,
+}
+impl CommonWorshipOptions {
+    /** Read the options from a file. */
+    pub fn read<R>(reader: R) -> Result<Self, calendar::CalendarError>
+    where
+        R: std::io::Read,
+    {
+        ron::de::from_reader(reader).map_err(calendar::CalendarError::from_error)
+    }
+    /** Follow an optional practice e.g. from the command line. */
+    pub fn enable(&mut self, practice: Practice) {
+        match practice {
+            Practice::EpiphanyOnSunday => self.epiphany_on_sunday = true,
+            Practice::PresentationOnSunday => self.presentation_on_sunday = true,
+            Practice::AllSaintsOnSunday => self.all_saints_on_sunday = true,
+            Practice::MatthiasInFebruary => self.matthias_in_february = true,
+            Practice::ThomasInDecember => self.thomas_in_december = true,
+            Practice::CorpusChristiAsFestival => self.corpus_christi_as_festival = true,
+        }
+    }
+    /** The holy day as kept with these options: the same holy day, or
+    a copy with another date or class.
+
+    ```
+    use anglican_calendar::calendar::{DateCal, Holyday, HolydayRef};
+    use anglican_calendar::year_calendar::CommonWorshipOptions;
+    let thomas = HolydayRef::new(Holyday {
+        tag: "thomas".to_string(),
+        date_cal: DateCal::Fixed { month: 7, day: 3 },
+        ..Holyday::default()
+    });
+    let options = CommonWorshipOptions {
+        thomas_in_december: true,
+        ..CommonWorshipOptions::default()
+    };
+    assert_eq!(
+        DateCal::Fixed { month: 12, day: 21 },
+        options.apply(&thomas).date_cal()
+    );
+    assert_eq!(thomas, CommonWorshipOptions::default().apply(&thomas));
+    ```
+    */
+    pub fn apply(&self, holyday: &calendar::HolydayRef) -> calendar::HolydayRef {
+        let sunday = |month, day| calendar::DateCal::Nearest {
+            date: Box::new(calendar::DateCal::Fixed { month, day }),
+            day_of_week: calendar::OrderableDayOfWeek::from(chrono::Weekday::Sun),
+            exclusive: false,
+        };
+        let fixed = |month, day| calendar::DateCal::Fixed { month, day };
+        let tag = holyday.tag();
+        let date_cal = holyday.date_cal();
+        for (on, moved_tag, usual, alternative) in [
+            (
+                self.epiphany_on_sunday,
+                "epiphany",
+                fixed(1, 6),
+                sunday(1, 5),
+            ),
+            (
+                self.presentation_on_sunday,
+                "presentation",
+                fixed(2, 2),
+                sunday(1, 31),
+            ),
+            (
+                self.all_saints_on_sunday,
+                "all saints",
+                fixed(11, 1),
+                sunday(11, 2),
+            ),
+            (
+                self.matthias_in_february,
+                "matthias",
+                fixed(5, 14),
+                fixed(2, 24),
+            ),
+            (
+                self.thomas_in_december,
+                "thomas",
+                fixed(7, 3),
+                fixed(12, 21),
+            ),
+        ]
+        .iter()
+        .cloned()
+        {
+            if on && tag == moved_tag && date_cal == usual {
+                return calendar::HolydayRef::new(calendar::Holyday {
+                    date_cal: alternative,
+                    ..calendar::Holyday::from(holyday.clone())
+                });
+            }
+        }
+        if self.corpus_christi_as_festival
+            && holyday.class() == calendar::HolydayClass::CorpusChristi
+        {
+            return calendar::HolydayRef::new(calendar::Holyday {
+                class: calendar::HolydayClass::Festival,
+                ..calendar::Holyday::from(holyday.clone())
+            });
+        }
+        holyday.clone()
+    }
+}
+/** An optional practice of Common Worship, see [CommonWorshipOptions]. */
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum Practice {
+    /** the Epiphany on a Sunday */
+    EpiphanyOnSunday,
+    /** the Presentation on a Sunday */
+    PresentationOnSunday,
+    /** All Saints on a Sunday */
+    AllSaintsOnSunday,
+    /** St Matthias in February */
+    MatthiasInFebruary,
+    /** St Thomas in December */
+    ThomasInDecember,
+    /** Corpus Christi as a Festival */
+    CorpusChristiAsFestival,
+}
+impl std::str::FromStr for Practice {
+    type Err = calendar::CalendarError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "epiphany-on-sunday" => Ok(Practice::EpiphanyOnSunday),
+            "presentation-on-sunday" | "candlemas-on-sunday" => Ok(Practice::PresentationOnSunday),
+            "all-saints-on-sunday" => Ok(Practice::AllSaintsOnSunday),
+            "matthias-in-february" => Ok(Practice::MatthiasInFebruary),
+            "thomas-in-december" => Ok(Practice::ThomasInDecember),
+            "corpus-christi-as-festival" => Ok(Practice::CorpusChristiAsFestival),
+            other => Err(calendar::CalendarError::new(&format!(
+                "unknown practice {}",
+                other
+            ))),
+        }
+    }
 }
 impl YearCalendar {
-    /** Create a YearCalendar from a [Calendar] given the year. */
+    /** Create a YearCalendar from a [Calendar] given the year.
+
+    If the options specify a Church year, `year` is the calendar year
+    in which the Church year ends e.g. 2021 for the Church year that
+    starts on Advent Sunday 2020. */
     pub fn from_calendar(
         calendar: &calendar::Calendar,
         year: i32,
+        options: &YearCalendarOptions,
         verbose: bool,
     ) -> Result<Self, calendar::CalendarError> {
-        let y = Year::new(year);
+        let y = Year::with_reckoning(year, calendar.easter);
         let mut ycal = Self {
             year: y.clone(),
             province: calendar.province,
+            church_year: options.church_year,
             holydays_by_date: HashMap::new(),
+            has_readings: false,
+            colours: colours::ColourRules::common_worship()?,
+            errors: vec![],
+            dropped: vec![],
+            decisions: vec![],
         };
+        let mut yee = vec![];
         for e in calendar.get_holydays() {
-            let mut ye = YearHolyday::from_holyday(&e, &ycal.year);
-            println!(
-                "{}",
-                Green.bold().paint(format!(
-                    "converting {} ({:?}) {}",
-                    e.title(),
-                    e.class(),
-                    ye.colour(&y)
-                ))
+            let e = options.practices.apply(&e);
+            let ye = if options.church_year {
+                match y.church_year_date(&e.date_cal(), e.leap_day()) {
+                    Ok(Some(date)) => YearHolyday::new(&e, date),
+                    Ok(None) => {
+                        if verbose {
+                            eprintln!(
+                                "{}",
+                                Yellow.paint(format!(
+                                    "{} does not occur in the Church year {}",
+                                    e.title(),
+                                    y.church_year_name()
+                                ))
+                            );
+                        }
+                        continue;
+                    }
+                    Err(err) => {
+                        ycal.date_error(&e, err);
+                        continue;
+                    }
+                }
+            } else {
+                match YearHolyday::from_holyday(&e, &ycal.year) {
+                    Ok(ye) => ye,
+                    Err(err) => {
+                        ycal.date_error(&e, err);
+                        continue;
+                    }
+                }
+            };
+            yee.push(ye);
+        }
+        if options.sundays {
+            /* a named Sunday that is already in the calendar (e.g.
+            Trinity Sunday) is not generated again */
+            let tags: HashSet<String> = calendar.get_holydays().iter().map(|e| e.tag()).collect();
+            yee.extend(
+                ycal.temporale(calendar::SundayScheme::from(calendar.province))
+                    .into_iter()
+                    .filter(|ye| !tags.contains(&ye.holyday.tag())),
             );
-            ycal.add(&mut ye, &y, verbose)?;
+        }
+        if calendar.days_of_prayer {
+            yee.append(&mut ycal.days_of_prayer());
+        }
+        /* place the holy days in order of precedence, and those that
+        cannot be transferred first and those with rules in the calendar
+        file last, so that each only has to give way to those already
+        placed. The holy days with the built-in rules (e.g. George) are
+        placed in date order with the others, so that George, moved out
+        of Easter Week, keeps its Monday when Philip and James is also
+        moved there off a Sunday. The order does not depend on the order
+        of the calendar. The named Sundays of Advent, Lent and Eastertide
+        come straight after the Principal Feasts, and the other Sundays
+        after the Festivals, see [Precedence]. */
+        yee.sort_by_cached_key(|ye| {
+            let transfer = ye.holyday.transfer();
+            (
+                Precedence::of(ye.holyday.class(), ye.date, &y),
+                transfer != calendar::TransferType::DoNotTransfer,
+                matches!(transfer, calendar::TransferType::Rules(_)),
+                ye.date,
+                ye.holyday.tag(),
+                ye.holyday.title(),
+            )
+        });
+        for mut ye in yee {
+            if verbose {
+                eprintln!(
+                    "{}",
+                    Green.bold().paint(format!(
+                        "converting {} ({:?}) {}",
+                        ye.holyday.title(),
+                        ye.holyday.class(),
+                        ye.colour(&y, &ycal.colours)
+                    ))
+                );
+            }
+            if let Err(err) = ycal.add(&mut ye, &y, verbose) {
+                ycal.date_error(&ye.holyday, err);
+            }
+        }
+        if options.eves {
+            ycal.add_eves(verbose);
         }
         Ok(ycal)
     }
+    /** Record that the date of an holy day could not be calculated,
+    so the holy day is left out of the calendar. */
+    fn date_error(&mut self, holyday: &calendar::HolydayRef, err: calendar::CalendarError) {
+        let err = calendar::CalendarError::new(&format!(
+            "cannot calculate the date of {} ({}): {}",
+            holyday.tag(),
+            holyday.title(),
+            err.message()
+        ));
+        self.errors.push(err);
+    }
+    /** The errors for the holy days whose dates could not be
+    calculated, which are left out of the calendar. */
+    pub fn errors(&self) -> &[calendar::CalendarError] {
+        &self.errors
+    }
+    /** Add the eves of the holy days that have them, on the day before
+    the (possibly transferred) holy day. There is no eve if the day
+    before is a Principal Feast. */
+    fn add_eves(&mut self, verbose: bool) {
+        let (start, _end) = self.date_range();
+        let principal_dates: HashSet<NaiveDate> = self
+            .holydays_by_date
+            .values()
+            .flatten()
+            .filter(|ye| ye.holyday.class() == calendar::HolydayClass::Principal)
+            .map(|ye| ye.date)
+            .collect();
+        let mut eves: Vec<YearHolyday> = self
+            .holydays_by_date
+            .values()
+            .flatten()
+            .filter(|ye| ye.holyday.has_eve() && !ye.eve)
+            .map(|ye| YearHolyday {
+                eve: true,
+                ..YearHolyday::new(&ye.holyday, ye.date - Duration::days(1))
+            })
+            .collect();
+        eves.sort_by(|a, b| a.date.cmp(&b.date).then(a.title().cmp(&b.title())));
+        for eve in eves {
+            if eve.date < start {
+                continue;
+            }
+            if principal_dates.contains(&eve.date) {
+                if verbose {
+                    eprintln!(
+                        "{}",
+                        Yellow.paint(format!(
+                            "no {} because {} is a principal feast",
+                            eve.title(),
+                            eve.date
+                        ))
+                    );
+                }
+                continue;
+            }
+            self.holydays_by_date.entry(eve.date).or_default().push(eve);
+        }
+    }
+    /** Add the Scripture readings to the holy days and Sundays of the
+    calendar, using the Sunday cycle of the Church year that contains
+    each date. Eves do not get readings. Returns the titles of any
+    Principal Feasts that have no readings, so that they can be
+    supplied. */
+    pub fn add_readings(&mut self, readings: &readings::Readings) -> Vec<String> {
+        let mut missing = vec![];
+        for ye in self.holydays_by_date.values_mut().flatten() {
+            if ye.eve {
+                continue;
+            }
+            let sunday_cycle = self.year.lectionary(ye.date).sunday;
+            ye.readings = readings.find(&ye.holyday.tag(), sunday_cycle).cloned();
+            if ye.is_missing_readings() {
+                missing.push(ye.title());
+            }
+        }
+        missing.sort();
+        self.has_readings = true;
+        missing
+    }
+    /** Use the colour rules of a province instead of the Common Worship
+    rules. */
+    pub fn set_colour_rules(&mut self, rules: colours::ColourRules) {
+        self.colours = rules;
+    }
+    /** the first date and the day after the last date of the calendar */
+    fn date_range(&self) -> (NaiveDate, NaiveDate) {
+        if self.church_year {
+            (self.year.advent_previous, self.year.advent_next)
+        } else {
+            (
+                Year::ymd(self.year.ad, 1, 1),
+                Year::ymd(self.year.ad + 1, 1, 1),
+            )
+        }
+    }
+    /** Generate the Ember Days and Rogation Days of the calendar. These
+    have the [calendar::HolydayClass::DayOfPrayer] class, so they are
+    never transferred or dropped. */
+    fn days_of_prayer(&self) -> Vec<YearHolyday> {
+        let (start, end) = self.date_range();
+        let mut yee = vec![];
+        for year in &[self.year.other(self.year.ad - 1), self.year.clone()] {
+            let ember_seasons = [
+                ("lent", "the first Sunday of Lent"),
+                ("pentecost", "the Day of Pentecost"),
+                ("holy cross", "Holy Cross Day"),
+                ("lucy", "St Lucy's Day"),
+            ];
+            let ember_days = ember_seasons
+                .iter()
+                .flat_map(|s| {
+                    ["Wednesday", "Friday", "Saturday"]
+                        .iter()
+                        .map(move |wd| (*s, *wd))
+                })
+                .zip(year.ember_days())
+                .map(|((season, wd), date)| {
+                    (
+                        "Ember Day",
+                        format!("Ember {} after {}", wd, season.1),
+                        format!("ember {} {}", season.0, wd.to_lowercase()),
+                        date,
+                    )
+                });
+            let rogation_days = ["Monday", "Tuesday", "Wednesday"]
+                .iter()
+                .zip(year.rogation_days())
+                .map(|(wd, date)| {
+                    (
+                        "Rogation Day",
+                        format!("Rogation {} before Ascension Day", wd),
+                        format!("rogation {}", wd.to_lowercase()),
+                        date,
+                    )
+                });
+            for (title, description, tag, date) in ember_days.chain(rogation_days) {
+                if date < start || date >= end {
+                    continue;
+                }
+                let holyday = calendar::Holyday {
+                    title: title.to_string(),
+                    description,
+                    class: calendar::HolydayClass::DayOfPrayer,
+                    tag,
+                    date_cal: calendar::DateCal::Fixed {
+                        month: date.month() as u8,
+                        day: date.day() as u8,
+                    },
+                    transfer: calendar::TransferType::DoNotTransfer,
+                    ..calendar::Holyday::default()
+                };
+                yee.push(YearHolyday::new(&calendar::HolydayRef::new(holyday), date));
+            }
+        }
+        yee
+    }
+    /** Generate the named Sundays of the calendar (the temporale) e.g. "2nd
+    Sunday of Easter". These have the [calendar::HolydayClass::Sunday]
+    class, so they are dropped if they clash with a Principal Feast. */
+    fn temporale(&self, scheme: calendar::SundayScheme) -> Vec<YearHolyday> {
+        let (start, end) = self.date_range();
+        let mut yee = vec![];
+        let mut sunday = Year::next_inclusive(start, chrono::Weekday::Sun);
+        while sunday < end {
+            let (tag, title) = self.year.sunday(sunday, scheme);
+            let holyday = calendar::Holyday {
+                title: title.clone(),
+                description: title.clone(),
+                class: calendar::HolydayClass::Sunday,
+                tag,
+                date_cal: calendar::DateCal::Fixed {
+                    month: sunday.month() as u8,
+                    day: sunday.day() as u8,
+                },
+                ..calendar::Holyday::default()
+            };
+            yee.push(YearHolyday::new(
+                &calendar::HolydayRef::new(holyday),
+                sunday,
+            ));
+            sunday += Duration::weeks(1);
+        }
+        yee
+    }
+    /** The title of the calendar e.g. "Church of England 2021" or, for a
+    Church year, "Church of England Year B 2020–21". */
+    pub fn title(&self) -> String {
+        if self.church_year {
+            format!("{} {}", self.province, self.year.church_year_name())
+        } else {
+            format!("{} {}", self.province, self.year.ad)
+        }
+    }
+    /** The lectionary years in use during the calendar e.g. "Year A,
+    Weekday Year 2, Daily Office Year 2 until 28 November 2020; Year B,
+    Weekday Year 1, Daily Office Year 1 from 29 November 2020" */
+    pub fn lectionary_summary(&self) -> String {
+        if self.church_year {
+            self.year.church_year_lectionary().to_string()
+        } else {
+            let advent = self.year.advent_next;
+            format!(
+                "{} until {}; {} from {}",
+                self.year.lectionary(Year::ymd(self.year.ad, 1, 1)),
+                (advent - Duration::days(1)).format("%e %B %Y"),
+                self.year.lectionary(advent),
+                advent.format("%e %B %Y")
+            )
+        }
+    }
+    /** The holy days kept on a date, the most important first. */
+    pub fn on(&self, date: NaiveDate) -> Vec<&YearHolyday> {
+        self.between(date, date)
+    }
+    /** The holy days kept from `start` to `end` (both included), in
+    date order and the most important first on each date.
+
+    ```
+    use anglican_calendar::calendar::{Calendar, DateCal, Holyday, HolydayClass};
+    use anglican_calendar::year_calendar::{YearCalendar, YearCalendarOptions};
+    use chrono::NaiveDate;
+    let mut cal = Calendar::new();
+    cal.add(&Holyday {
+        title: "The Annunciation".to_string(),
+        tag: "annunciation".to_string(),
+        class: HolydayClass::Principal,
+        date_cal: DateCal::Fixed { month: 3, day: 25 },
+        ..Holyday::default()
+    });
+    let ycal =
+        YearCalendar::from_calendar(&cal, 2021, &YearCalendarOptions::default(), false).unwrap();
+    let on = ycal.on(NaiveDate::from_ymd(2021, 3, 25));
+    assert_eq!(1, on.len());
+    assert_eq!("The Annunciation", on[0].title());
+    assert!(ycal
+        .between(NaiveDate::from_ymd(2021, 3, 26), NaiveDate::from_ymd(2021, 12, 31))
+        .is_empty());
+    ```
+    */
+    pub fn between(&self, start: NaiveDate, end: NaiveDate) -> Vec<&YearHolyday> {
+        let mut yee: Vec<&YearHolyday> = self
+            .holydays_by_date
+            .values()
+            .flatten()
+            .filter(|ye| ye.date >= start && ye.date <= end)
+            .collect();
+        yee.sort_by(|a, b| {
+            a.date
+                .cmp(&b.date)
+                .then(b.holyday.class().cmp(&a.holyday.class()))
+                .then(a.eve.cmp(&b.eve))
+        });
+        yee
+    }
+    /** The next `n` holy days kept on or after a date, in date order. */
+    pub fn next_n(&self, date: NaiveDate, n: usize) -> Vec<&YearHolyday> {
+        let mut yee = self.between(date, NaiveDate::MAX);
+        yee.truncate(n);
+        yee
+    }
+    /** A one-line description of a holy day in this calendar, with its
+    class, its colour and any transfer e.g. "The Annunciation
+    (Principal, White) transferred from Thursday 25 March" */
+    pub fn describe(&self, ye: &YearHolyday) -> String {
+        let mut description = format!(
+            "{} ({}, {})",
+            ye.title(),
+            ye.holyday.class(),
+            self.colours
+                .holyday_colour(&ye.holyday, &self.year, ye.date)
+        );
+        if ye.is_transferred() {
+            description.push_str(&format!(
+                " transferred from {}",
+                ye.nominal_date.format("%A %-d %B")
+            ));
+            if let Some(reason) = ye.transfer_reason {
+                description.push_str(&format!(" ({})", reason));
+            }
+        }
+        description
+    }
+    /** The dates on which an holy day is kept this year, after any
+    transfer, without its eve. This is usually one date, but may be
+    none if the holy day is dropped, see [YearCalendar::dropped_by_tag].
+
+    ```
+    use anglican_calendar::calendar::{Calendar, DateCal, Holyday, HolydayClass};
+    use anglican_calendar::year_calendar::{TransferReason, YearCalendar, YearCalendarOptions};
+    use chrono::NaiveDate;
+    let mut cal = Calendar::new();
+    cal.add(&Holyday {
+        title: "Cuthbert".to_string(),
+        tag: "cuthbert".to_string(),
+        class: HolydayClass::Festival,
+        date_cal: DateCal::Fixed { month: 3, day: 20 },
+        ..Holyday::default()
+    });
+    // 20 March 2022 is the 3rd Sunday of Lent
+    let ycal =
+        YearCalendar::from_calendar(&cal, 2022, &YearCalendarOptions::default(), false).unwrap();
+    let found = ycal.find_by_tag("cuthbert");
+    assert_eq!(1, found.len());
+    assert_eq!(NaiveDate::from_ymd(2022, 3, 21), found[0].date());
+    assert_eq!(NaiveDate::from_ymd(2022, 3, 20), found[0].nominal_date());
+    assert_eq!(Some(TransferReason::Sunday), found[0].transfer_reason());
+    assert!(ycal.find_by_tag("aidan").is_empty());
+    ```
+    */
+    pub fn find_by_tag(&self, tag: &str) -> Vec<&YearHolyday> {
+        let mut yee: Vec<&YearHolyday> = self
+            .holydays_by_date
+            .values()
+            .flatten()
+            .filter(|ye| !ye.eve && ye.holyday.tag() == tag)
+            .collect();
+        yee.sort();
+        yee
+    }
+    /** The holy days with a tag that are not kept this year, with the
+    reason they were dropped. */
+    pub fn dropped_by_tag(&self, tag: &str) -> Vec<(&YearHolyday, DropReason)> {
+        self.dropped
+            .iter()
+            .filter(|(ye, _)| ye.holyday.tag() == tag)
+            .map(|(ye, r)| (ye, *r))
+            .collect()
+    }
+    /** Why each holy day was kept, moved or dropped, in the order
+    they were decided. */
+    pub fn decisions(&self) -> &[Decision] {
+        &self.decisions
+    }
+    /** The decisions for the holy days with a tag, from the nominal
+    date to the date on which it is kept or dropped. An holy day that is
+    transferred has a decision on each date it was tried.
+
+    ```
+    use anglican_calendar::calendar::{Calendar, DateCal, Holyday, HolydayClass};
+    use anglican_calendar::year_calendar::{
+        DropReason, Outcome, PlacementRule, YearCalendar, YearCalendarOptions,
+    };
+    use chrono::NaiveDate;
+    let mut cal = Calendar::new();
+    for (title, tag, class) in [
+        ("The Annunciation", "annunciation", HolydayClass::Principal),
+        ("A Lesser Festival", "lesser", HolydayClass::LesserFestival),
+    ] {
+        cal.add(&Holyday {
+            title: title.to_string(),
+            tag: tag.to_string(),
+            class,
+            date_cal: DateCal::Fixed { month: 3, day: 25 },
+            ..Holyday::default()
+        });
+    }
+    let ycal =
+        YearCalendar::from_calendar(&cal, 2021, &YearCalendarOptions::default(), false).unwrap();
+    let why = ycal.why("lesser");
+    assert_eq!(1, why.len());
+    assert_eq!(NaiveDate::from_ymd(2021, 3, 25), why[0].date());
+    assert_eq!(Outcome::Dropped(DropReason::Clash), why[0].outcome());
+    assert_eq!(PlacementRule::GivesWay, why[0].rule());
+    assert_eq!(Some("annunciation".to_string()), why[0].clash().map(|c| c.tag()));
+    assert_eq!(None, why[0].final_date());
+    ```
+    */
+    pub fn why(&self, tag: &str) -> Vec<&Decision> {
+        self.decisions
+            .iter()
+            .filter(|d| d.holyday.tag() == tag)
+            .collect()
+    }
     /** Generate an iCalendar. Also generate the calendar updates to cancel the entries.
 
     See [RFC 5545](https://tools.ietf.org/html/rfc5545) and [RFC
@@ -58,82 +713,145 @@ impl YearCalendar {
 
     pub fn to_ical(&self, unique: &str) -> (icalendar::Calendar, icalendar::Calendar) {
         let mut ical = icalendar::Calendar::new();
+        ical.name(&self.title());
 
         /* Not sure how to do this: add calendar properties here
         e.g. set REFRESH-INTERVAL to P4W i.e. refresh every 4
         weeks */
 
         let mut ical_del = icalendar::Calendar::new();
-        println!("unique code for holydays is {}", unique);
-        let mut ix = 0;
+        self.push_ical_events(&mut ical, &mut ical_del, unique);
+        (ical, ical_del)
+    }
+    /** Add the events for the holy days to an iCalendar and the
+    cancellations to another, with the UIDs from [YearHolyday::uid]
+    (with "-2", "-3" etc. added if it is repeated on the date). */
+    fn push_ical_events(
+        &self,
+        ical: &mut icalendar::Calendar,
+        ical_del: &mut icalendar::Calendar,
+        unique: &str,
+    ) {
+        let scheme = calendar::SundayScheme::from(self.province);
         let mut dates: Vec<&NaiveDate> = self.holydays_by_date.keys().collect();
         dates.sort();
         for d in dates {
-            let yee = &self.holydays_by_date[&d];
+            let yee = &self.holydays_by_date[d];
+            let mut seen: HashMap<String, usize> = HashMap::new();
             for ye in yee {
-                let uid = format!("{}-{}", unique, ix);
+                let uid = ye.uid(unique);
+                /* the same holy day can be on the same date more than
+                once (e.g. in a merged calendar) so later ones are
+                numbered */
+                let count = seen.entry(uid.clone()).or_insert(0);
+                *count += 1;
+                let uid = if *count == 1 {
+                    uid
+                } else {
+                    format!("{}-{}", uid, count)
+                };
                 let mut e1a = icalendar::Event::new();
-                let e = e1a
-                    .summary(&ye.holyday.title())
-                    .description(&ye.holyday.description())
+                e1a.summary(&ye.title())
+                    .description(&format!(
+                        "{}\n{}{}",
+                        ye.holyday.description(),
+                        self.year.lectionary(ye.date),
+                        match &ye.readings {
+                            Some(readings) => format!("\nReadings: {}", readings.join("; ")),
+                            None => "".to_string(),
+                        }
+                    ))
                     .all_day(Utc.from_utc_date(&ye.date))
                     .uid(&uid)
                     .append_property(icalendar::Property::new("TRANSP", "TRANSPARENT"))
                     .append_property(icalendar::Property::new("SEQUENCE", "0"))
-                    .append_property(icalendar::Property::new("COLOR", &ye.colour(&self.year)))
-                    .done();
-                ical.push(e);
+                    .append_property(icalendar::Property::new(
+                        "COLOR",
+                        &ye.colour(&self.year, &self.colours),
+                    ));
+                let mut categories = vec![self.year.season(ye.date).to_string()];
+                if let Some(sub_season) = self.year.sub_season(ye.date, scheme) {
+                    categories.push(sub_season.to_string());
+                }
+                if ye.holyday.class() == calendar::HolydayClass::DayOfPrayer {
+                    categories.push("Day of Prayer".to_string());
+                }
+                e1a.append_property(icalendar::Property::new(
+                    "CATEGORIES",
+                    &categories.join(",").to_uppercase(),
+                ));
+                ical.push(e1a.done());
                 let e_del = icalendar::Event::new()
                     .uid(&uid)
                     .append_property(icalendar::Property::new("STATUS", "CANCELLED"))
                     .append_property(icalendar::Property::new("SEQUENCE", "1"))
                     .done();
                 ical_del.push(e_del);
-                ix += 1;
             }
         }
-        (ical, ical_del)
     }
     /** Write a human-readable report to a file. */
     pub fn write_report(&self, w: &mut dyn Write) -> Result<(), calendar::CalendarError> {
-        let mut rt = ReportTemplate {
-            dates: vec![],
+        let rt = ReportTemplate {
+            dates: self.report_dates(),
             year: self.year.ad,
             province: self.province.to_string(),
+            title: self.title(),
+            lectionary: self.lectionary_summary(),
         };
+        let r = rt.render().map_err(calendar::CalendarError::from_error)?;
+        w.write_all(r.as_bytes())
+            .map_err(calendar::CalendarError::from_error)
+        //        Ok(())
+    }
+    /** the dates of the calendar for the report */
+    fn report_dates(&self) -> Vec<ReportDate> {
+        let mut report_dates = vec![];
+        let scheme = calendar::SundayScheme::from(self.province);
         let mut dates: Vec<&NaiveDate> = self.holydays_by_date.keys().collect();
         dates.sort();
         for d in dates {
-            let season_colour = self.year.season_colour(*d);
+            let season_colour = self.colours.season_colour(&self.year, *d);
             let mut rd = ReportDate {
                 date: *d,
                 date_form: d.format("%A %B %e").to_string(),
                 holydays: vec![],
                 colour_a: season_colour.colour_a(),
                 colour_b: season_colour.colour_b(),
+                season: self.year.season_name(*d, scheme),
+                decisions: self
+                    .decisions
+                    .iter()
+                    .filter(|dn| dn.date == *d && dn.outcome != Outcome::Kept)
+                    .map(|dn| dn.to_string())
+                    .collect(),
             };
-            let yee = &self.holydays_by_date[&d];
+            let yee = &self.holydays_by_date[d];
             for ye in yee {
                 let mut refs_format: Vec<(String, String)> = vec![];
                 for r in &ye.holyday.refs() {
                     refs_format.push((r.url(), r.description.clone()));
                 }
                 let rhd = ReportHolyday {
-                    title: ye.holyday.title().clone(),
+                    title: ye.title(),
                     description: ye.holyday.description().clone(),
                     other: ye.holyday.other().clone(),
                     refs_format,
                     class_format: ye.holyday.class().to_string(),
+                    day_of_prayer: ye.holyday.class() == calendar::HolydayClass::DayOfPrayer,
+                    readings: ye.readings.clone().unwrap_or_default(),
+                    missing_readings: self.has_readings && ye.is_missing_readings(),
                 };
                 rd.holydays.push(rhd);
             }
-            rt.dates.push(rd);
+            report_dates.push(rd);
         }
-        let r = rt.render().map_err(calendar::CalendarError::from_error)?;
-        w.write_all(r.as_bytes())
-            .map_err(calendar::CalendarError::from_error)
-        //        Ok(())
+        report_dates
     }
+    /** Add an holy day to the calendar. If the holy day is transferred,
+    the transfer rules are applied again on the new date, and so on
+    until it can be kept (or is dropped), so it is stored under the date
+    on which it is kept. */
     fn add(
         &mut self,
         ye: &mut YearHolyday,
@@ -141,46 +859,143 @@ impl YearCalendar {
         verbose: bool,
     ) -> Result<(), calendar::CalendarError> {
         if verbose {
-            println!("for {} adding {}", ye.date, ye.holyday.title());
+            eprintln!("for {} adding {}", ye.date, ye.holyday.title());
         }
-        if let Some(day_holydays) = self.holydays_by_date.get_mut(&ye.date) {
-            Self::add_holyday_if_ok(day_holydays, ye, year, verbose)?;
-        } else {
-            if verbose {
-                println!("new date {}", ye.date);
+        let first_decision = self.decisions.len();
+        for _ in 0..MAX_TRANSFERS {
+            let date = ye.date;
+            if verbose && !self.holydays_by_date.contains_key(&date) {
+                eprintln!("new date {}", date);
             }
-            let mut de = vec![];
-            Self::add_holyday_if_ok(&mut de, ye, year, verbose)?;
-            self.holydays_by_date.insert(ye.date, de); // may insert empty list, is ok
-        };
-        Ok(())
+            let day_holydays = self.holydays_by_date.entry(date).or_default(); // may insert empty list, is ok
+            let scheme = calendar::TransferScheme::from(self.province);
+            let (placement, replaced) =
+                match Self::add_holyday_if_ok(day_holydays, ye, year, scheme, verbose) {
+                    Ok(placed) => placed,
+                    Err(err) => {
+                        /* do not leave an empty date behind */
+                        if day_holydays.is_empty() {
+                            self.holydays_by_date.remove(&date);
+                        }
+                        return Err(err);
+                    }
+                };
+            for sunday in replaced {
+                let decision = Decision {
+                    holyday: sunday.holyday.clone(),
+                    nominal_date: sunday.nominal_date,
+                    date,
+                    outcome: Outcome::Dropped(DropReason::Clash),
+                    rule: PlacementRule::FestivalReplacesSunday,
+                    clash: Some(ye.holyday.clone()),
+                    final_date: None,
+                };
+                /* the Sunday was kept, so that decision is replaced */
+                match self.decisions.iter_mut().find(|d| {
+                    d.holyday == sunday.holyday && d.date == date && d.outcome == Outcome::Kept
+                }) {
+                    Some(kept) => *kept = decision,
+                    None => self.decisions.push(decision),
+                }
+                self.dropped.push((sunday, DropReason::Clash));
+            }
+            let outcome = match placement.status {
+                DropStatus::Keep if ye.date != date => Outcome::Moved(ye.date),
+                DropStatus::Keep => Outcome::Kept,
+                DropStatus::Drop(r) => Outcome::Dropped(r),
+            };
+            let decision = Decision {
+                holyday: ye.holyday.clone(),
+                nominal_date: ye.nominal_date,
+                date,
+                outcome,
+                rule: placement.rule,
+                clash: placement.clash,
+                final_date: None,
+            };
+            if verbose && matches!(outcome, Outcome::Moved(_)) {
+                eprintln!("{}", Yellow.bold().paint(decision.to_string()));
+            }
+            self.decisions.push(decision);
+            match outcome {
+                Outcome::Moved(_) => {
+                    /* transferred, so check the new date */
+                    continue;
+                }
+                Outcome::Kept => {
+                    for d in &mut self.decisions[first_decision..] {
+                        if d.holyday == ye.holyday {
+                            d.final_date = Some(date);
+                        }
+                    }
+                }
+                Outcome::Dropped(r) => self.dropped.push((ye.clone(), r)),
+            }
+            return Ok(());
+        }
+        Err(calendar::CalendarError::new(&format!(
+            "{} transferred more than {} times",
+            ye.holyday.title(),
+            MAX_TRANSFERS
+        )))
     }
     fn add_holyday_if_ok(
         day_holydays: &mut Vec<YearHolyday>,
         ye: &mut YearHolyday,
         year: &Year,
-        _verbose: bool,
-    ) -> Result<(), calendar::CalendarError> {
-        let ds = Self::fix_holyday_date_is_ok(day_holydays, ye, year);
-        match ds {
+        scheme: calendar::TransferScheme,
+        verbose: bool,
+    ) -> Result<(Placement, Vec<YearHolyday>), calendar::CalendarError> {
+        let date = ye.date;
+        let placement = Self::place(day_holydays, ye, year, scheme)?;
+        let mut replaced = vec![];
+        match &placement.status {
+            DropStatus::Keep if ye.date != date => {
+                /* transferred, so added by the caller on the new date */
+            }
             DropStatus::Keep => {
+                let c = ye.holyday.class();
+                if (c == calendar::HolydayClass::Festival
+                    || c == calendar::HolydayClass::CorpusChristi)
+                    && !year.sunday_has_precedence(date)
+                {
+                    /* a festival kept on a Sunday replaces the Sunday */
+                    day_holydays.retain(|e| {
+                        let is_sunday = e.holyday.class() == calendar::HolydayClass::Sunday;
+                        if is_sunday {
+                            replaced.push(e.clone());
+                            if verbose {
+                                eprintln!(
+                                    "{}",
+                                    Yellow.bold().paint(format!(
+                                        "{} ({}) replaced by {}",
+                                        e.holyday.title(),
+                                        e.date,
+                                        ye.holyday.title()
+                                    ))
+                                );
+                            }
+                        }
+                        !is_sunday
+                    });
+                }
                 day_holydays.push(ye.clone());
             }
             DropStatus::Drop(r) => {
-                // if verbose {
-                println!(
-                    "{}",
-                    Yellow.bold().paint(format!(
-                        "{} ({}) dropped because {:?}",
-                        ye.holyday.title(),
-                        ye.date,
-                        r
-                    ))
-                );
-                // }
+                if verbose {
+                    eprintln!(
+                        "{}",
+                        Yellow.bold().paint(format!(
+                            "{} ({}) dropped because {:?}",
+                            ye.holyday.title(),
+                            ye.date,
+                            r
+                        ))
+                    );
+                }
             }
         }
-        Ok(())
+        Ok((placement, replaced))
     }
     /**
      Tests if an holyday exists for the current year and, if necessary,
@@ -194,94 +1009,144 @@ impl YearCalendar {
     Note that some lesser festivals will be dropped altogether e.g. if
     they appear on a Sunday but commemorations appearing on the same day
     will not be dropped.
+
+    The rules depend on the [calendar::TransferScheme] of the
+    province.
+
+    Returns an error if a date needed for the transfer rules cannot be
+    calculated, or if the holy day cannot be placed (a Sunday that is
+    not on a Sunday, or an holy day that is not a festival).
                  */
     pub fn fix_holyday_date_is_ok(
         day_holydays: &[YearHolyday],
         ye: &mut YearHolyday,
         year: &Year,
-    ) -> DropStatus {
+        scheme: calendar::TransferScheme,
+    ) -> Result<DropStatus, calendar::CalendarError> {
+        Ok(Self::place(day_holydays, ye, year, scheme)?.status)
+    }
+    /** As [YearCalendar::fix_holyday_date_is_ok], also giving the rule
+    that was applied and the holy day that it clashed with. */
+    fn place(
+        day_holydays: &[YearHolyday],
+        ye: &mut YearHolyday,
+        year: &Year,
+        scheme: calendar::TransferScheme,
+    ) -> Result<Placement, calendar::CalendarError> {
         // calculate some dates and date ranges
 
         let is_sunday = ye.date.weekday() == chrono::Weekday::Sun;
-        if is_sunday {
-            println!("{} ({}) is sunday", ye.holyday.title(), ye.date);
-        }
         // let is_weekday = match ye.date.weekday() {
         //     chrono::Weekday::Sat | chrono::Weekday::Sun => false,
         //     _ => true,
         // };
-        let is_in_advent =
-            ye.date >= year.advent_next && ye.date < NaiveDate::from_ymd(year.ad, 12, 25);
-        if is_in_advent {
-            println!("{} ({}) is in advent", ye.holyday.title(), ye.date);
-        }
+        let is_in_advent = year.is_in_advent(ye.date);
         let is_in_lent_or_eastertide = ye.date >= year.ash_wednesday && ye.date <= year.pentecost;
-        if is_in_lent_or_eastertide {
-            println!(
-                "{} ({}) is in lent or eastertide",
-                ye.holyday.title(),
-                ye.date
-            );
-        }
         //        let is_in_holy_week = ye.date >= year.palm_sunday && ye.date < year.easter;
         let is_in_easter = ye.date >= year.palm_sunday && ye.date <= year.easter_sunday_2;
-        if is_in_easter {
-            println!("{} ({}) is in easter", ye.holyday.title(), ye.date);
-        }
 
+        let t = ye.holyday.transfer();
+        let c = ye.holyday.class();
         let day_has_holyday = !day_holydays.is_empty();
         let mut clash_level = calendar::HolydayClass::NotAFestival;
+        let mut clash = None;
         //      let mut multi_level = false;
         if day_has_holyday {
-            for ce in day_holydays.iter() {
+            /* ignore holy days that have already been transferred away */
+            for ce in day_holydays.iter().filter(|e| e.date == ye.date) {
                 let cel = ce.holyday.class();
+                /* festivals are not displaced by ordinary Sundays */
+                if cel == calendar::HolydayClass::Sunday
+                    && (c == calendar::HolydayClass::Festival
+                        || c == calendar::HolydayClass::CorpusChristi)
+                {
+                    continue;
+                }
                 if cel > clash_level {
                     clash_level = cel;
+                    clash = Some(ce.holyday.clone());
                     //   multi_level = true;
                 }
             }
-            print!(
-                "date clash {} already {} holydays: ",
-                &ye.date,
-                day_holydays.len()
-            );
-            for e in day_holydays.iter() {
-                print!("{}, ", e.holyday.title());
-            }
-            print!("{}", ye.holyday.title());
-            println!();
         }
 
-        let t = ye.holyday.transfer();
-        let c = ye.holyday.class();
         let clash_higher = day_has_holyday && clash_level > c;
-        match t {
+        let is_feast_of_our_lord = FEASTS_OF_OUR_LORD.contains(&ye.holyday.tag().as_str());
+        let (status, rule) = match t {
+            /* the 1662 Prayer Book has no rules of transfer, so its
+            holy days are kept on their own dates */
+            calendar::TransferType::Normal
+            | calendar::TransferType::Annunciation
+            | calendar::TransferType::Joseph
+            | calendar::TransferType::George
+            | calendar::TransferType::Mark
+                if scheme == calendar::TransferScheme::CommonPrayer1662
+                    && c != calendar::HolydayClass::Sunday =>
+            {
+                (DropStatus::Keep, PlacementRule::CommonPrayer1662)
+            }
             // TODO no 'saints days' in Easter Week
             calendar::TransferType::Normal => match c {
                 calendar::HolydayClass::Commemoration => {
                     /* no transfer required */
                     if is_in_easter {
-                        DropStatus::Drop(DropReason::Easter)
+                        (
+                            DropStatus::Drop(DropReason::Easter),
+                            PlacementRule::NotInEaster,
+                        )
+                    } else if is_sunday
+                        && scheme != calendar::TransferScheme::CommonWorship
+                        && !is_feast_of_our_lord
+                    {
+                        (
+                            DropStatus::Drop(DropReason::Sunday),
+                            PlacementRule::NotOnSunday,
+                        )
                     } else {
-                        DropStatus::Keep
+                        (DropStatus::Keep, PlacementRule::NoConflict)
                     }
                 }
                 calendar::HolydayClass::LesserFestival => {
                     if is_in_easter {
-                        DropStatus::Drop(DropReason::Easter)
+                        (
+                            DropStatus::Drop(DropReason::Easter),
+                            PlacementRule::NotInEaster,
+                        )
                     } else if clash_higher {
-                        DropStatus::Drop(DropReason::Clash)
+                        (DropStatus::Drop(DropReason::Clash), PlacementRule::GivesWay)
                     } else if is_sunday {
-                        DropStatus::Drop(DropReason::Sunday)
+                        (
+                            DropStatus::Drop(DropReason::Sunday),
+                            PlacementRule::NotOnSunday,
+                        )
                     } else {
-                        DropStatus::Keep
+                        (DropStatus::Keep, PlacementRule::NoConflict)
                     }
                 }
                 calendar::HolydayClass::Festival | calendar::HolydayClass::CorpusChristi => {
-                    if (is_sunday && (is_in_advent || is_in_lent_or_eastertide)) || clash_higher {
-                        ye.change_date_by(Duration::days(1))
-                    }
-                    DropStatus::Keep
+                    let bcp_1979 = scheme == calendar::TransferScheme::CommonPrayer1979;
+                    let sunday_first = if bcp_1979 {
+                        !is_feast_of_our_lord || year.sunday_has_precedence(ye.date)
+                    } else {
+                        is_in_advent || is_in_lent_or_eastertide
+                    };
+                    let rule = if bcp_1979 && is_in_easter {
+                        /* to the week after the 2nd Sunday of Easter */
+                        ye.change_date_to(
+                            year.easter_sunday_2 + Duration::days(1),
+                            TransferReason::Easter,
+                        );
+                        PlacementRule::OutOfEaster
+                    } else if is_sunday && sunday_first {
+                        ye.change_date_by(Duration::days(1), TransferReason::Sunday);
+                        PlacementRule::SundayFirst
+                    } else if clash_higher || (bcp_1979 && day_has_holyday && clash_level >= c) {
+                        ye.change_date_by(Duration::days(1), TransferReason::Clash);
+                        PlacementRule::GivesWay
+                    } else {
+                        PlacementRule::NoConflict
+                    };
+                    (DropStatus::Keep, rule)
                 }
                 calendar::HolydayClass::Principal => {
                     // if day_has_holyday {
@@ -291,166 +1156,723 @@ impl YearCalendar {
                     //     // )))
                     //     DropStatus::Drop(DropReason::Clash) // ??
                     // } else {
-                    DropStatus::Keep
+                    (DropStatus::Keep, PlacementRule::NotTransferred)
                     // }
                 }
                 calendar::HolydayClass::Sunday => {
                     /* what about Annunciation?? */
-                    assert!(is_sunday);
-                    if clash_higher {
-                        DropStatus::Drop(DropReason::Clash)
+                    if !is_sunday {
+                        return Err(calendar::CalendarError::new(&format!(
+                            "{} is a Sunday but {} is not",
+                            ye.holyday.title(),
+                            ye.date
+                        )));
+                    }
+                    /* a festival replaces a Sunday outside Advent, Lent and Eastertide */
+                    let has_festival = day_holydays.iter().any(|e| {
+                        e.date == ye.date
+                            && (e.holyday.class() == calendar::HolydayClass::Festival
+                                || e.holyday.class() == calendar::HolydayClass::CorpusChristi)
+                    });
+                    /* only one Sunday for each date */
+                    if day_has_holyday && clash_level >= c {
+                        (DropStatus::Drop(DropReason::Clash), PlacementRule::GivesWay)
+                    } else if day_has_holyday
+                        && has_festival
+                        && !year.sunday_has_precedence(ye.date)
+                    {
+                        (
+                            DropStatus::Drop(DropReason::Clash),
+                            PlacementRule::FestivalReplacesSunday,
+                        )
                     } else {
-                        DropStatus::Keep
+                        (DropStatus::Keep, PlacementRule::NoConflict)
                     }
                 }
                 calendar::HolydayClass::Unclassified => {
                     /* no transfer required? */
-                    DropStatus::Keep
+                    (DropStatus::Keep, PlacementRule::NotTransferred)
+                }
+                calendar::HolydayClass::DayOfPrayer => {
+                    /* kept alongside any holy day */
+                    (DropStatus::Keep, PlacementRule::NotTransferred)
+                }
+                calendar::HolydayClass::NotAFestival => {
+                    return Err(calendar::CalendarError::new(&format!(
+                        "{} is not a festival so cannot be placed",
+                        ye.holyday.title()
+                    )))
                 }
-                calendar::HolydayClass::NotAFestival => panic!("bad class"),
             },
-            calendar::TransferType::Annunciation => {
-                if is_sunday {
-                    ye.change_date_by(Duration::days(1))
-                }
-                DropStatus::Keep
-            }
-            // calendar::TransferType::BaptismOfChrist => {
-            //     Ok(true)
-            // }
-            calendar::TransferType::Joseph => {
-                if is_in_easter {
-                    let abvm = NaiveDate::from_ymd(year.ad, 3, 25);
-                    let days = if abvm >= year.palm_sunday && abvm <= year.easter_sunday_2 {
-                        2
-                    } else {
-                        1
-                    };
-                    ye.change_date_to(year.easter_sunday_2 + Duration::days(days));
+            calendar::TransferType::Before(cutoff) => {
+                if ye.date >= year.date_cal_to_date(&cutoff, ye.holyday.leap_day())? {
+                    (DropStatus::Drop(DropReason::Cutoff), PlacementRule::Cutoff)
+                } else {
+                    (DropStatus::Keep, PlacementRule::NoConflict)
+                }
+            }
+            calendar::TransferType::DoNotTransfer => {
+                (DropStatus::Keep, PlacementRule::NotTransferred)
+            }
+            calendar::TransferType::Annunciation
+            | calendar::TransferType::Joseph
+            | calendar::TransferType::George
+            | calendar::TransferType::Mark
+            | calendar::TransferType::Rules(_) => {
+                let rules = t.rules()?.unwrap_or_default();
+                let (status, rule) =
+                    Self::apply_transfer_rules(&rules, ye, year, is_sunday, clash_level)?;
+                if let PlacementRule::TransferRule(n) = rule {
+                    if TransferReason::from_conditions(&rules[n - 1].when) != TransferReason::Clash
+                    {
+                        clash = None;
+                    }
                 }
-                DropStatus::Keep
+                (status, rule)
             }
-            calendar::TransferType::George => {
-                if is_in_easter {
-                    ye.change_date_to(year.easter_sunday_2 + Duration::days(1))
+        };
+        /* only name the holy day it clashed with if that decided it */
+        let clash = match rule {
+            PlacementRule::GivesWay | PlacementRule::FestivalReplacesSunday => clash,
+            PlacementRule::TransferRule(_) => clash,
+            _ => None,
+        };
+        Ok(Placement {
+            status,
+            rule,
+            clash,
+        })
+    }
+    /** Apply the first of the [calendar::TransferRule]s whose
+    conditions all hold to an holy day. */
+    fn apply_transfer_rules(
+        rules: &[calendar::TransferRule],
+        ye: &mut YearHolyday,
+        year: &Year,
+        is_sunday: bool,
+        clash_level: calendar::HolydayClass,
+    ) -> Result<(DropStatus, PlacementRule), calendar::CalendarError> {
+        let leap_day = ye.holyday.leap_day();
+        let in_period = |date: NaiveDate,
+                         period: &calendar::TransferPeriod|
+         -> Result<bool, calendar::CalendarError> {
+            Ok(match period {
+                calendar::TransferPeriod::Advent => year.is_in_advent(date),
+                calendar::TransferPeriod::LentOrEastertide => {
+                    date >= year.ash_wednesday && date <= year.pentecost
+                }
+                calendar::TransferPeriod::Easter => {
+                    date >= year.palm_sunday && date <= year.easter_sunday_2
+                }
+                calendar::TransferPeriod::Between(from, to) => {
+                    date >= year.date_cal_to_date(from, leap_day)?
+                        && date <= year.date_cal_to_date(to, leap_day)?
+                }
+            })
+        };
+        for (n, rule) in rules.iter().enumerate() {
+            let mut holds = true;
+            for condition in &rule.when {
+                holds = match condition {
+                    calendar::TransferCondition::OnSunday => is_sunday,
+                    calendar::TransferCondition::In(period) => in_period(ye.date, period)?,
+                    calendar::TransferCondition::DateIn(date_cal, period) => {
+                        in_period(year.date_cal_to_date(date_cal, leap_day)?, period)?
+                    }
+                    calendar::TransferCondition::ClashesWith(class) => clash_level >= *class,
+                };
+                if !holds {
+                    break;
                 }
-                DropStatus::Keep
             }
-            calendar::TransferType::Mark => {
-                if is_in_easter {
-                    let sgd = ye.date - Duration::days(2);
-                    let days = if sgd >= year.palm_sunday && sgd <= year.easter_sunday_2 {
+            if !holds {
+                continue;
+            }
+            let reason = TransferReason::from_conditions(&rule.when);
+            let status = match &rule.action {
+                calendar::TransferAction::Keep => DropStatus::Keep,
+                calendar::TransferAction::NextDay => {
+                    ye.change_date_by(Duration::days(1), reason);
+                    DropStatus::Keep
+                }
+                calendar::TransferAction::NextWeekday => {
+                    let days = if ye.date.weekday() == chrono::Weekday::Sat {
                         2
                     } else {
                         1
                     };
-                    ye.change_date_to(year.easter_sunday_2 + Duration::days(days));
+                    ye.change_date_by(Duration::days(days), reason);
+                    DropStatus::Keep
                 }
-                DropStatus::Keep
-            }
-            calendar::TransferType::Before(cutoff) => {
-                if ye.date >= year.date_cal_to_date(&cutoff) {
-                    DropStatus::Drop(DropReason::Cutoff)
-                } else {
+                calendar::TransferAction::MoveTo(date_cal) => {
+                    ye.change_date_to(year.date_cal_to_date(date_cal, leap_day)?, reason);
                     DropStatus::Keep
                 }
-            }
-            calendar::TransferType::DoNotTransfer => DropStatus::Keep,
+                calendar::TransferAction::Drop => DropStatus::Drop(match reason {
+                    TransferReason::Sunday => DropReason::Sunday,
+                    TransferReason::Clash => DropReason::Clash,
+                    TransferReason::Easter => DropReason::Easter,
+                    TransferReason::Rule => DropReason::Other,
+                }),
+            };
+            return Ok((status, PlacementRule::TransferRule(n + 1)));
         }
+        Ok((DropStatus::Keep, PlacementRule::NoTransferRule))
     }
 }
-/** whether a [YearHolyday] will be dropped. */
-#[derive(Debug, Eq, PartialEq)]
-pub enum DropStatus {
-    Keep,
-    Drop(DropReason),
-}
-/** reason for dropping a [YearHolyday] */
-#[derive(Debug, Eq, PartialEq)]
-pub enum DropReason {
-    Easter,
-    Clash,
-    Sunday,
-    Cutoff,
-    Other,
-}
-#[derive(Debug, Eq, PartialEq, Clone)]
-/** A YearHolyday is an holyday in the calendar for a specific year
-([YearCalendar]) e.g. in the 2019 calendar of the Anglican Church of
-Hong Kong, Easter Sunday was 21 April and Matteo Ricci was 11 May. */
-pub struct YearHolyday {
-    holyday: calendar::HolydayRef,
-    date: NaiveDate,
+/** A CalendarRange is the [YearCalendar]s of a province for a range
+of consecutive years (or Church years) e.g. the calendars of the
+Church of England from 2021 to 2030. */
+#[derive(Debug)]
+pub struct CalendarRange {
+    calendars: Vec<YearCalendar>,
 }
-impl YearHolyday {
-    /** Create a [YearHolyday] from an [calendar::Holyday] given the [Year] data. */
-    pub fn from_holyday(holyday: &calendar::HolydayRef, year: &Year) -> Self {
-        Self {
-            holyday: holyday.clone(),
-            date: year.date_cal_to_date(&holyday.date_cal()),
+impl CalendarRange {
+    /** Create the [YearCalendar]s for a range of years from a
+    [calendar::Calendar], with the same options for each year. */
+    pub fn from_calendar(
+        calendar: &calendar::Calendar,
+        years: RangeInclusive<i32>,
+        options: &YearCalendarOptions,
+        verbose: bool,
+    ) -> Result<Self, calendar::CalendarError> {
+        if years.is_empty() {
+            return Err(calendar::CalendarError::new(&format!(
+                "no years in {}–{}",
+                years.start(),
+                years.end()
+            )));
         }
+        let calendars = years
+            .map(|year| YearCalendar::from_calendar(calendar, year, options, verbose))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { calendars })
     }
-    /** Change the date of a [YearHolyday] by a specified [Duration] */
-    pub fn change_date_by(&mut self, cd: Duration) {
-        self.date += cd;
-        println!(
-            "{}",
-            Yellow.bold().paint(format!(
-                "{} ({:?}/{:?}) changed to {} (modified by {:?})",
-                self.holyday.title(),
-                self.holyday.class(),
-                self.holyday.transfer(),
-                &self.date,
-                cd
-            ))
-        );
+    /** the calendars for each year, in order */
+    pub fn calendars(&self) -> &[YearCalendar] {
+        &self.calendars
     }
-    /** set the date of a [YearHolyday] to the specified date */
-    pub fn change_date_to(&mut self, d: NaiveDate) {
-        self.date = d;
-        println!(
-            "{}",
-            Yellow.bold().paint(format!(
-                "{} ({:?}/{:?}) changed to {}",
-                self.holyday.title(),
-                self.holyday.class(),
-                self.holyday.transfer(),
-                &self.date
-            ))
-        );
+    /** Use the colour rules of a province for every year, see
+    [YearCalendar::set_colour_rules]. */
+    pub fn set_colour_rules(&mut self, rules: colours::ColourRules) {
+        for ycal in &mut self.calendars {
+            ycal.set_colour_rules(rules.clone());
+        }
     }
-    /** the display colour for this holy day, from the CSS3 set of
-    colour names, see [colours](https://www.w3.org/TR/css-color-3) */
-    pub fn colour(&self, year: &Year) -> String {
-        /* "If the Collect, Readings, etc. on a Lesser Festival are
-        those of the saint, then either red (for a martyr) or white is
-        used; " */
-        match self.holyday.class() {
-            // calendar::HolydayClass::Principal
-            // | calendar::HolydayClass::CorpusChristi
-            // |
-            calendar::HolydayClass::Festival | calendar::HolydayClass::LesserFestival => {
-                let is_martyr = self
-                    .holyday
-                    .main()
-                    .contains(&calendar::MainAttribute::Martyr);
-                let fest_col = if is_martyr { "red" } else { "white" };
-                println!(
-                    "{} has colour {} for {:?}",
-                    self.holyday.title(),
-                    fest_col,
-                    self.holyday.class()
-                );
-                fest_col.to_string()
+    /** Add the Scripture readings for every year, see
+    [YearCalendar::add_readings]. Returns the titles of the Principal
+    Feasts that have no readings in any of the years. */
+    pub fn add_readings(&mut self, readings: &readings::Readings) -> Vec<String> {
+        let mut missing: Vec<String> = self
+            .calendars
+            .iter_mut()
+            .flat_map(|ycal| ycal.add_readings(readings))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+    /** the errors of all the years, see [YearCalendar::errors] */
+    pub fn errors(&self) -> Vec<&calendar::CalendarError> {
+        self.calendars
+            .iter()
+            .flat_map(|ycal| ycal.errors())
+            .collect()
+    }
+    /** The title of the calendars e.g. "Church of England 2021–2030".
+    For a single year, this is the title of the [YearCalendar]. */
+    pub fn title(&self) -> String {
+        let first = &self.calendars[0];
+        let last = &self.calendars[self.calendars.len() - 1];
+        if self.calendars.len() == 1 {
+            first.title()
+        } else if first.church_year {
+            format!(
+                "{} Church years {}–{}",
+                first.province,
+                first.year.ad - 1,
+                last.year.ad
+            )
+        } else {
+            format!("{} {}–{}", first.province, first.year.ad, last.year.ad)
+        }
+    }
+    /** Generate a single iCalendar for all the years, and the
+    calendar updates to cancel the entries. The UIDs are from
+    [YearHolyday::uid], so they do not change if the calendars are
+    generated again, or for an overlapping range of years. */
+    pub fn to_ical(&self, unique: &str) -> (icalendar::Calendar, icalendar::Calendar) {
+        let mut ical = icalendar::Calendar::new();
+        ical.name(&self.title());
+        let mut ical_del = icalendar::Calendar::new();
+        for ycal in &self.calendars {
+            ycal.push_ical_events(&mut ical, &mut ical_del, unique);
+        }
+        (ical, ical_del)
+    }
+    /** Write a single human-readable report for all the years. */
+    pub fn write_report(&self, w: &mut dyn Write) -> Result<(), calendar::CalendarError> {
+        let first = &self.calendars[0];
+        let lectionary = if self.calendars.len() == 1 {
+            first.lectionary_summary()
+        } else {
+            self.calendars
+                .iter()
+                .map(|ycal| format!("{}: {}", ycal.title(), ycal.lectionary_summary()))
+                .collect::<Vec<String>>()
+                .join("; ")
+        };
+        let rt = ReportTemplate {
+            dates: self
+                .calendars
+                .iter()
+                .flat_map(|ycal| ycal.report_dates())
+                .collect(),
+            year: first.year.ad,
+            province: first.province.to_string(),
+            title: self.title(),
+            lectionary,
+        };
+        let r = rt.render().map_err(calendar::CalendarError::from_error)?;
+        w.write_all(r.as_bytes())
+            .map_err(calendar::CalendarError::from_error)
+    }
+}
+/** The precedence of an holy day on a date, the most important first,
+following the Common Worship "Rules to Order the Christian Year". The
+holy days of a [YearCalendar] are placed in this order, so an holy day
+is only transferred or dropped to make way for one that comes before
+it. */
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+pub enum Precedence {
+    /** Principal Feasts and Principal Holy Days */
+    Principal,
+    /** the Sundays of Advent, Lent and Eastertide */
+    PrincipalSunday,
+    /** Corpus Christi, when it is kept as more than a Festival */
+    CorpusChristi,
+    /** Festivals */
+    Festival,
+    /** the other Sundays */
+    Sunday,
+    /** Lesser Festivals */
+    LesserFestival,
+    /** Commemorations */
+    Commemoration,
+    /** holy days with no class */
+    Unclassified,
+    /** Ember and Rogation Days, which are kept alongside any holy day */
+    DayOfPrayer,
+}
+/** The [Precedence] of each [calendar::HolydayClass], in Advent, Lent
+and Eastertide and at other times. */
+const PRECEDENCE: [(calendar::HolydayClass, Precedence, Precedence); 9] = [
+    (
+        calendar::HolydayClass::Principal,
+        Precedence::Principal,
+        Precedence::Principal,
+    ),
+    (
+        calendar::HolydayClass::Sunday,
+        Precedence::PrincipalSunday,
+        Precedence::Sunday,
+    ),
+    (
+        calendar::HolydayClass::CorpusChristi,
+        Precedence::CorpusChristi,
+        Precedence::CorpusChristi,
+    ),
+    (
+        calendar::HolydayClass::Festival,
+        Precedence::Festival,
+        Precedence::Festival,
+    ),
+    (
+        calendar::HolydayClass::LesserFestival,
+        Precedence::LesserFestival,
+        Precedence::LesserFestival,
+    ),
+    (
+        calendar::HolydayClass::Commemoration,
+        Precedence::Commemoration,
+        Precedence::Commemoration,
+    ),
+    (
+        calendar::HolydayClass::Unclassified,
+        Precedence::Unclassified,
+        Precedence::Unclassified,
+    ),
+    (
+        calendar::HolydayClass::NotAFestival,
+        Precedence::Unclassified,
+        Precedence::Unclassified,
+    ),
+    (
+        calendar::HolydayClass::DayOfPrayer,
+        Precedence::DayOfPrayer,
+        Precedence::DayOfPrayer,
+    ),
+];
+impl Precedence {
+    /** The precedence of an holy day of a class on its nominal date.
+
+    ```
+    use anglican_calendar::calendar::HolydayClass;
+    use anglican_calendar::year_calendar::{Precedence, Year};
+    use chrono::NaiveDate;
+    let year = Year::new(2021);
+    let sunday = |m, d| Precedence::of(HolydayClass::Sunday, NaiveDate::from_ymd(2021, m, d), &year);
+    assert_eq!(Precedence::PrincipalSunday, sunday(3, 21)); // 5th Sunday of Lent
+    assert_eq!(Precedence::Sunday, sunday(6, 20));
+    assert!(sunday(3, 21) < Precedence::of(HolydayClass::Festival, NaiveDate::from_ymd(2021, 3, 21), &year));
+    assert!(sunday(6, 20) > Precedence::Festival);
+    ```
+    */
+    pub fn of(class: calendar::HolydayClass, date: NaiveDate, year: &Year) -> Self {
+        let (_, in_season, other) = PRECEDENCE
+            .iter()
+            .find(|(c, _, _)| *c == class)
+            .expect("class missing from precedence table");
+        if year.sunday_has_precedence(date) {
+            *in_season
+        } else {
+            *other
+        }
+    }
+}
+/** the tags of the Feasts of our Lord, which take the place of a Sunday
+outside Advent, Lent and Eastertide in the 1979 Book of Common Prayer */
+const FEASTS_OF_OUR_LORD: [&str; 6] = [
+    "baptism of christ",
+    "christ the king",
+    "holy name",
+    "naming circumcision",
+    "presentation",
+    "transfiguration",
+];
+/** the most times an holy day can be transferred before it is treated
+as an error, so that rules that keep moving an holy day cannot loop
+for ever */
+const MAX_TRANSFERS: usize = 31;
+/** whether a [YearHolyday] will be dropped. */
+#[derive(Debug, Eq, PartialEq)]
+pub enum DropStatus {
+    Keep,
+    Drop(DropReason),
+}
+/** reason for dropping a [YearHolyday] */
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum DropReason {
+    Easter,
+    Clash,
+    Sunday,
+    Cutoff,
+    Other,
+}
+impl fmt::Display for DropReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            DropReason::Easter => "in Holy Week or Easter Week",
+            DropReason::Clash => "clashes with a more important holy day",
+            DropReason::Sunday => "on a Sunday",
+            DropReason::Cutoff => "after its last date",
+            DropReason::Other => "by the transfer rules of the holy day",
+        })
+    }
+}
+/** the result of checking a [YearHolyday] on a date */
+struct Placement {
+    status: DropStatus,
+    rule: PlacementRule,
+    clash: Option<calendar::HolydayRef>,
+}
+/** the rule that decided where a [YearHolyday] is kept, see [Decision] */
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum PlacementRule {
+    /** nothing prevents the holy day being kept on the date */
+    NoConflict,
+    /** the holy day is never transferred e.g. a Principal Feast */
+    NotTransferred,
+    /** the Book of Common Prayer (1662) keeps every holy day on its
+    own date */
+    CommonPrayer1662,
+    /** a lesser holy day is not kept in Holy Week or Easter Week */
+    NotInEaster,
+    /** a lesser holy day is not kept on a Sunday */
+    NotOnSunday,
+    /** the holy day gives way to a more important holy day */
+    GivesWay,
+    /** the Sunday takes precedence over a Festival */
+    SundayFirst,
+    /** a Festival in Holy Week or Easter Week is moved to the week
+    after the 2nd Sunday of Easter */
+    OutOfEaster,
+    /** a Festival outside Advent, Lent and Eastertide takes the place
+    of the Sunday */
+    FestivalReplacesSunday,
+    /** the holy day is not kept after its last date */
+    Cutoff,
+    /** the nth (from 1) of the [calendar::TransferRule]s of the holy day */
+    TransferRule(usize),
+    /** none of the [calendar::TransferRule]s of the holy day applies */
+    NoTransferRule,
+}
+impl fmt::Display for PlacementRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlacementRule::NoConflict => f.write_str("nothing prevents it"),
+            PlacementRule::NotTransferred => f.write_str("it is not transferred"),
+            PlacementRule::CommonPrayer1662 => {
+                f.write_str("the 1662 Prayer Book keeps holy days on their own dates")
             }
-            _ => match year.season_colour(self.date) {
-                calendar::SeasonColour::White => "white".to_string(),
-                calendar::SeasonColour::Red => "red".to_string(),
-                calendar::SeasonColour::Purple => "purple".to_string(),
-                calendar::SeasonColour::Green => "green".to_string(),
-            },
+            PlacementRule::NotInEaster => {
+                f.write_str("lesser holy days are not kept in Holy Week or Easter Week")
+            }
+            PlacementRule::NotOnSunday => f.write_str("lesser holy days are not kept on a Sunday"),
+            PlacementRule::GivesWay => f.write_str("it gives way to a more important holy day"),
+            PlacementRule::SundayFirst => f.write_str("the Sunday takes precedence"),
+            PlacementRule::OutOfEaster => f.write_str(
+                "Festivals in Holy Week or Easter Week are moved to the week after the 2nd Sunday of Easter",
+            ),
+            PlacementRule::FestivalReplacesSunday => {
+                f.write_str("a Festival takes the place of the Sunday")
+            }
+            PlacementRule::Cutoff => f.write_str("it is after its last date"),
+            PlacementRule::TransferRule(n) => write!(f, "transfer rule {} of the holy day", n),
+            PlacementRule::NoTransferRule => {
+                f.write_str("none of the transfer rules of the holy day applies")
+            }
+        }
+    }
+}
+/** what happened to a [YearHolyday] on a date, see [Decision] */
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Outcome {
+    /** kept on the date */
+    Kept,
+    /** moved to another date, where it is checked again */
+    Moved(NaiveDate),
+    /** not kept this year */
+    Dropped(DropReason),
+}
+/** A Decision records why an holy day was kept, moved or dropped on a
+date when the [YearCalendar] was generated. An holy day that is moved
+is checked again on its new date, so it may have several decisions,
+see [YearCalendar::why]. */
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Decision {
+    holyday: calendar::HolydayRef,
+    /** the date before any transfer */
+    nominal_date: NaiveDate,
+    /** the date that was checked */
+    date: NaiveDate,
+    outcome: Outcome,
+    rule: PlacementRule,
+    /** the holy day that it clashed with, if any */
+    clash: Option<calendar::HolydayRef>,
+    /** the date on which the holy day is finally kept, if it is kept */
+    final_date: Option<NaiveDate>,
+}
+impl Decision {
+    /** the holy day */
+    pub fn holyday(&self) -> calendar::HolydayRef {
+        self.holyday.clone()
+    }
+    /** the date of the holy day before any transfer */
+    pub fn nominal_date(&self) -> NaiveDate {
+        self.nominal_date
+    }
+    /** the date that was checked */
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+    /** whether the holy day was kept, moved or dropped */
+    pub fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+    /** the rule that was applied */
+    pub fn rule(&self) -> PlacementRule {
+        self.rule
+    }
+    /** the holy day on the date that it clashed with, if any */
+    pub fn clash(&self) -> Option<calendar::HolydayRef> {
+        self.clash.clone()
+    }
+    /** the date on which the holy day is finally kept, or none if it
+    is dropped */
+    pub fn final_date(&self) -> Option<NaiveDate> {
+        self.final_date
+    }
+}
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}): ",
+            self.holyday.title(),
+            self.date.format("%A %-d %B")
+        )?;
+        match self.outcome {
+            Outcome::Kept => f.write_str("kept")?,
+            Outcome::Moved(to) => write!(f, "moved to {}", to.format("%A %-d %B"))?,
+            Outcome::Dropped(r) => write!(f, "dropped ({})", r)?,
+        }
+        write!(f, " because {}", self.rule)?;
+        if let Some(clash) = &self.clash {
+            write!(f, ", clashes with {}", clash.title())?;
+        }
+        Ok(())
+    }
+}
+/** reason for transferring a [YearHolyday] to another date */
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum TransferReason {
+    /** on a Sunday of Advent, Lent or Easter */
+    Sunday,
+    /** on the same day as a more important holy day */
+    Clash,
+    /** in Holy Week or Easter Week */
+    Easter,
+    /** by another of the [calendar::TransferRule]s of the holy day */
+    Rule,
+}
+impl TransferReason {
+    /** the reason for a [calendar::TransferRule] with these conditions */
+    fn from_conditions(conditions: &[calendar::TransferCondition]) -> Self {
+        let has = |f: &dyn Fn(&calendar::TransferCondition) -> bool| conditions.iter().any(f);
+        if has(&|c| matches!(c, calendar::TransferCondition::ClashesWith(_))) {
+            TransferReason::Clash
+        } else if has(&|c| *c == calendar::TransferCondition::OnSunday) {
+            TransferReason::Sunday
+        } else if has(&|c| *c == calendar::TransferCondition::In(calendar::TransferPeriod::Easter))
+        {
+            TransferReason::Easter
+        } else {
+            TransferReason::Rule
+        }
+    }
+}
+impl fmt::Display for TransferReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            TransferReason::Sunday => "on a Sunday",
+            TransferReason::Clash => "clashes with a more important holy day",
+            TransferReason::Easter => "in Holy Week or Easter Week",
+            TransferReason::Rule => "by the transfer rules of the holy day",
+        })
+    }
+}
+#[derive(Debug, Eq, PartialEq, Clone)]
+/** A YearHolyday is an holyday in the calendar for a specific year
+([YearCalendar]) e.g. in the 2019 calendar of the Anglican Church of
+Hong Kong, Easter Sunday was 21 April and Matteo Ricci was 11 May. */
+pub struct YearHolyday {
+    holyday: calendar::HolydayRef,
+    date: NaiveDate,
+    /** the date before any transfer */
+    nominal_date: NaiveDate,
+    /** why the holy day was transferred, if it was */
+    transfer_reason: Option<TransferReason>,
+    /** this is the eve of the holy day, not the holy day itself */
+    eve: bool,
+    /** the Scripture readings, if any were found in the [readings::Readings] */
+    readings: Option<Vec<String>>,
+}
+impl YearHolyday {
+    /** Create a [YearHolyday] for an [calendar::Holyday] on a date. */
+    pub fn new(holyday: &calendar::HolydayRef, date: NaiveDate) -> Self {
+        Self {
+            holyday: holyday.clone(),
+            date,
+            nominal_date: date,
+            transfer_reason: None,
+            eve: false,
+            readings: None,
+        }
+    }
+    /** Create a [YearHolyday] from an [calendar::Holyday] given the [Year] data. */
+    pub fn from_holyday(
+        holyday: &calendar::HolydayRef,
+        year: &Year,
+    ) -> Result<Self, calendar::CalendarError> {
+        Ok(Self::new(
+            holyday,
+            year.date_cal_to_date(&holyday.date_cal(), holyday.leap_day())?,
+        ))
+    }
+    /** the holy day */
+    pub fn holyday(&self) -> &calendar::HolydayRef {
+        &self.holyday
+    }
+    /** the date the holy day is kept, after any transfer */
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+    /** the date of the holy day before any transfer */
+    pub fn nominal_date(&self) -> NaiveDate {
+        self.nominal_date
+    }
+    /** whether the holy day has been transferred to another date */
+    pub fn is_transferred(&self) -> bool {
+        self.date != self.nominal_date
+    }
+    /** why the holy day was transferred, if it was */
+    pub fn transfer_reason(&self) -> Option<TransferReason> {
+        self.transfer_reason
+    }
+    /** whether this is the eve of the holy day */
+    pub fn is_eve(&self) -> bool {
+        self.eve
+    }
+    /** An iCalendar UID that is the same whenever the calendar is
+    generated, from the date and the tag e.g.
+    "ang-20210325-annunciation" */
+    pub fn uid(&self, unique: &str) -> String {
+        format!(
+            "{}-{}-{}{}",
+            unique,
+            self.date.format("%Y%m%d"),
+            self.holyday.tag().replace(' ', "-"),
+            if self.eve { "-eve" } else { "" }
+        )
+    }
+    /** the Scripture readings, if any have been found */
+    pub fn readings(&self) -> Option<&Vec<String>> {
+        self.readings.as_ref()
+    }
+    /** whether this is a Principal Feast with no readings */
+    fn is_missing_readings(&self) -> bool {
+        !self.eve
+            && self.readings.is_none()
+            && self.holyday.class() == calendar::HolydayClass::Principal
+    }
+    /** the title for display e.g. "Eve of Christmas Day" for an eve */
+    pub fn title(&self) -> String {
+        if self.eve {
+            format!("Eve of {}", self.holyday.title())
+        } else {
+            self.holyday.title()
         }
     }
+    /** Change the date of a [YearHolyday] by a specified [Duration] */
+    pub fn change_date_by(&mut self, cd: Duration, reason: TransferReason) {
+        self.date += cd;
+        /* keep the reason for the first transfer, from the nominal date */
+        self.transfer_reason.get_or_insert(reason);
+    }
+    /** set the date of a [YearHolyday] to the specified date */
+    pub fn change_date_to(&mut self, d: NaiveDate, reason: TransferReason) {
+        self.date = d;
+        /* keep the reason for the first transfer, from the nominal date */
+        self.transfer_reason.get_or_insert(reason);
+    }
+    /** the display colour for this holy day, from the CSS3 set of
+    colour names, see [colours](https://www.w3.org/TR/css-color-3) */
+    pub fn colour(&self, year: &Year, rules: &colours::ColourRules) -> String {
+        rules
+            .holyday_colour(&self.holyday, year, self.date)
+            .colour_a()
+    }
 }
 impl Ord for YearHolyday {
     fn cmp(&self, other: &Self) -> Ordering {
@@ -468,6 +1890,8 @@ impl PartialOrd for YearHolyday {
 struct ReportTemplate {
     province: String,
     year: i32,
+    title: String,
+    lectionary: String,
     dates: Vec<ReportDate>,
 }
 #[derive(Debug, Clone)]
@@ -477,12 +1901,18 @@ struct ReportDate {
     holydays: Vec<ReportHolyday>,
     colour_a: String,
     colour_b: String,
+    season: String,
+    /** why holy days were moved from or dropped on the date */
+    decisions: Vec<String>,
 }
 #[derive(Debug, Clone)]
 struct ReportHolyday {
     title: String,
     description: String,
     class_format: String,
+    day_of_prayer: bool,
+    readings: Vec<String>,
+    missing_readings: bool,
     other: Vec<String>,
     refs_format: Vec<(String, String)>,
 }
@@ -499,8 +1929,12 @@ inclusive. */
 pub struct Year {
     /** year AD/CE for the calendar. */
     pub ad: i32,
-    /** date of Easter Sunday */
+    /** how Easter Day is reckoned */
+    pub reckoning: calendar::EasterReckoning,
+    /** date of Easter Sunday, by the reckoning of the year */
     pub easter: NaiveDate,
+    /** date of Orthodox Easter Sunday (Pascha) */
+    pub orthodox_easter: NaiveDate,
     /** date of previous Advent Sunday */
     pub advent_previous: NaiveDate,
     /** date of next Advent Sunday */
@@ -517,12 +1951,15 @@ pub struct Year {
 impl Year {
     /** a year with calculated dates
      ```
+    use anglican_calendar::calendar::EasterReckoning;
     use anglican_calendar::year_calendar::Year;
     use chrono::NaiveDate;
     assert_eq!(
         Year {
             ad: 2020,
+            reckoning: EasterReckoning::Gregorian,
             easter: NaiveDate::from_ymd(2020, 4, 12),
+            orthodox_easter: NaiveDate::from_ymd(2020, 4, 19),
             advent_previous: NaiveDate::from_ymd(2019, 12, 1),
             advent_next: NaiveDate::from_ymd(2020, 11, 29),
             ash_wednesday: NaiveDate::from_ymd(2020, 2, 26),
@@ -535,7 +1972,9 @@ impl Year {
     assert_eq!(
         Year {
             ad: 2019,
+            reckoning: EasterReckoning::Gregorian,
             easter: NaiveDate::from_ymd(2019, 4, 21),
+            orthodox_easter: NaiveDate::from_ymd(2019, 4, 28),
             advent_previous: NaiveDate::from_ymd(2018, 12, 2),
             advent_next: NaiveDate::from_ymd(2019, 12, 1),
             ash_wednesday: NaiveDate::from_ymd(2019, 3, 6),
@@ -548,17 +1987,43 @@ impl Year {
      ```
      */
     pub fn new(year: i32) -> Self {
-        let easter =
-            NaiveDate::from_ymd(year, 3, 1) + Duration::days(i64::from(Self::computus(year) - 1));
+        Self::with_reckoning(year, calendar::EasterReckoning::Gregorian)
+    }
+    /** a year with calculated dates, with Easter Day and the dates that
+    depend on it reckoned as specified e.g. for a calendar that follows
+    the Orthodox Pascha.
+
+    ```
+    use anglican_calendar::calendar::EasterReckoning;
+    use anglican_calendar::year_calendar::Year;
+    use chrono::NaiveDate;
+    let year = Year::with_reckoning(2021, EasterReckoning::Julian);
+    assert_eq!(NaiveDate::from_ymd(2021, 5, 2), year.easter);
+    assert_eq!(NaiveDate::from_ymd(2021, 6, 20), year.pentecost);
+    assert_eq!(
+        NaiveDate::from_ymd(2021, 4, 4),
+        year.easter_by(EasterReckoning::Gregorian)
+    );
+    ```
+    */
+    pub fn with_reckoning(year: i32, reckoning: calendar::EasterReckoning) -> Self {
+        let easter = match reckoning {
+            calendar::EasterReckoning::Gregorian => Self::computus(year),
+            calendar::EasterReckoning::Julian => Self::computus_julian(year),
+        };
+        let easter = Year::ymd(year, 3, 1) + Duration::days(i64::from(easter - 1));
         Self {
             ad: year,
+            reckoning,
             easter,
+            orthodox_easter: Year::ymd(year, 3, 1)
+                + Duration::days(i64::from(Self::computus_julian(year) - 1)),
             advent_previous: Year::previous_inclusive(
-                Year::next_inclusive(NaiveDate::from_ymd(year - 1, 12, 1), chrono::Weekday::Thu),
+                Year::next_inclusive(Year::ymd(year - 1, 12, 1), chrono::Weekday::Thu),
                 chrono::Weekday::Sun,
             ),
             advent_next: Year::previous_inclusive(
-                Year::next_inclusive(NaiveDate::from_ymd(year, 12, 1), chrono::Weekday::Thu),
+                Year::next_inclusive(Year::ymd(year, 12, 1), chrono::Weekday::Thu),
                 chrono::Weekday::Sun,
             ),
             ash_wednesday: easter - Duration::days(46),
@@ -567,112 +2032,694 @@ impl Year {
             pentecost: easter + Duration::days(49),
         }
     }
-    fn date_cal_to_date(&self, date_cal: &calendar::DateCal) -> NaiveDate {
-        match date_cal {
+    /** The date of a [calendar::DateCal] in this year. An holy day on
+    29 February goes to 28 February or 1 March in a year that is not a
+    leap year, depending on `leap_day`. Returns an error if there is
+    no such date e.g. 30 February or the 5th Sunday of a month that
+    only has four.
+
+    ```
+    use anglican_calendar::calendar::{DateCal, LeapDayPolicy};
+    use anglican_calendar::year_calendar::Year;
+    use chrono::NaiveDate;
+    let leap_day = DateCal::Fixed { month: 2, day: 29 };
+    assert_eq!(
+        NaiveDate::from_ymd(2020, 2, 29),
+        Year::new(2020).date_cal_to_date(&leap_day, LeapDayPolicy::March1).unwrap()
+    );
+    assert_eq!(
+        NaiveDate::from_ymd(2021, 3, 1),
+        Year::new(2021).date_cal_to_date(&leap_day, LeapDayPolicy::March1).unwrap()
+    );
+    assert!(Year::new(2021)
+        .date_cal_to_date(&DateCal::Fixed { month: 2, day: 30 }, LeapDayPolicy::February28)
+        .is_err());
+    ```
+    */
+    pub fn date_cal_to_date(
+        &self,
+        date_cal: &calendar::DateCal,
+        leap_day: calendar::LeapDayPolicy,
+    ) -> Result<NaiveDate, calendar::CalendarError> {
+        Ok(match date_cal {
             calendar::DateCal::Easter => self.easter,
+            calendar::DateCal::OrthodoxEaster => self.orthodox_easter,
             calendar::DateCal::Advent => self.advent_previous,
             calendar::DateCal::AdventNext => self.advent_next,
             calendar::DateCal::Fixed { month, day } => {
-                NaiveDate::from_ymd(self.ad, u32::from(*month), u32::from(*day))
+                Year::fixed_date(self.ad, u32::from(*month), u32::from(*day), leap_day)?
+            }
+            calendar::DateCal::Bissextile { month, day } => {
+                let date = Year::fixed_date(self.ad, u32::from(*month), u32::from(*day), leap_day)?;
+                let is_leap_year = NaiveDate::from_ymd_opt(self.ad, 2, 29).is_some();
+                if is_leap_year && *month == 2 && *day >= 24 {
+                    date + Duration::days(1)
+                } else {
+                    date
+                }
             }
             calendar::DateCal::After { date, rel } => {
-                self.date_cal_to_date(date) + Duration::days(i64::from(*rel))
+                self.date_cal_to_date(date, leap_day)? + Duration::days(i64::from(*rel))
             }
             calendar::DateCal::Next { date, day_of_week } => Year::next_inclusive(
-                self.date_cal_to_date(date),
+                self.date_cal_to_date(date, leap_day)?,
                 chrono::Weekday::from(day_of_week.clone()),
             ),
-        }
+            calendar::DateCal::Previous {
+                date,
+                day_of_week,
+                exclusive,
+            } => {
+                if *exclusive {
+                    Year::previous_exclusive(
+                        self.date_cal_to_date(date, leap_day)?,
+                        chrono::Weekday::from(day_of_week.clone()),
+                    )
+                } else {
+                    Year::previous_inclusive(
+                        self.date_cal_to_date(date, leap_day)?,
+                        chrono::Weekday::from(day_of_week.clone()),
+                    )
+                }
+            }
+            calendar::DateCal::Nearest {
+                date,
+                day_of_week,
+                exclusive,
+            } => {
+                if *exclusive {
+                    Year::nearest_exclusive(
+                        self.date_cal_to_date(date, leap_day)?,
+                        chrono::Weekday::from(day_of_week.clone()),
+                    )
+                } else {
+                    Year::nearest(
+                        self.date_cal_to_date(date, leap_day)?,
+                        chrono::Weekday::from(day_of_week.clone()),
+                    )
+                }
+            }
+            calendar::DateCal::NthWeekdayOfMonth {
+                month,
+                n,
+                day_of_week,
+            } => {
+                let date = Year::next_inclusive(
+                    Year::fixed_date(self.ad, u32::from(*month), 1, leap_day)?,
+                    chrono::Weekday::from(day_of_week.clone()),
+                ) + Duration::weeks(i64::from(*n) - 1);
+                if *n == 0 || date.month() != u32::from(*month) {
+                    return Err(calendar::CalendarError::new(&format!(
+                        "there is no {} {:?} in month {} of {}",
+                        ordinal(i64::from(*n)),
+                        day_of_week.wd,
+                        month,
+                        self.ad
+                    )));
+                }
+                date
+            }
+            calendar::DateCal::LastWeekdayOfMonth { month, day_of_week } => {
+                Year::previous_inclusive(
+                    Year::last_day_of_month(self.ad, u32::from(*month))?,
+                    chrono::Weekday::from(day_of_week.clone()),
+                )
+            }
+        })
     }
-    /** Calculate the date of Easter Day. Returns result as number of days since March 0.
-
-    Uses Michael Behrend's version of Clavius’s original method, see
-    www.cantab.net/users/michael.behrend/algorithms/easter/pages/main.html */
-    pub fn computus(year: i32) -> i32 {
-        let c = year / 100;
-        let d = (3 * c - 5) / 4;
-        // (solar correction) + 10, also used for day of week
-        let e = (8 * c + 13) / 25; // (lunar correction) + 5
-        let f = year % 19; // (golden number of year) - 1
-                           // Get q, where q = 53 - (Clavius epact), so that
-                           //    q + 21 = date of Paschal full moon in days since March 0.
-                           // Value on left of % is always >= 0, so no worry there.
-        let mut q = (227 - 11 * f + d - e) % 30;
-        if (q == 29) || ((q == 28) && (f >= 11)) {
-            q -= 1;
+    /** a date from the month and day, applying the leap day policy to
+    29 February */
+    fn fixed_date(
+        year: i32,
+        month: u32,
+        day: u32,
+        leap_day: calendar::LeapDayPolicy,
+    ) -> Result<NaiveDate, calendar::CalendarError> {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .or_else(|| match (month, day, leap_day) {
+                (2, 29, calendar::LeapDayPolicy::February28) => {
+                    NaiveDate::from_ymd_opt(year, 2, 28)
+                }
+                (2, 29, calendar::LeapDayPolicy::March1) => NaiveDate::from_ymd_opt(year, 3, 1),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                calendar::CalendarError::new(&format!(
+                    "there is no day {} in month {} of {}",
+                    day, month, year
+                ))
+            })
+    }
+    /** the last day of a month */
+    fn last_day_of_month(year: i32, month: u32) -> Result<NaiveDate, calendar::CalendarError> {
+        if month == 12 {
+            Year::fixed_date(year, 12, 31, calendar::LeapDayPolicy::default())
+        } else {
+            Ok(
+                Year::fixed_date(year, month + 1, 1, calendar::LeapDayPolicy::default())?
+                    - Duration::days(1),
+            )
         }
-        // Get day of week of Paschal full moon (0 = Sun, 1 = Mon, ..., 6 = Sat)
-        let w = (year + (year / 4) - d + q) % 7;
-        // Get next Sunday strictly after Paschal full moon
-        q + 28 - w
     }
-    /** the next day being the specified weekday, not including the original date.
+    /** The date of a [calendar::DateCal] within the Church year that
+    ends in this year i.e. from the previous Advent Sunday up to the
+    eve of the next Advent Sunday. Dates that would fall after the
+    Church year (e.g. Christmas Day) are taken from the previous
+    calendar year. Returns `None` if the date does not occur in the
+    Church year, and an error if the date cannot be calculated.
 
     ```
+    use anglican_calendar::calendar::{DateCal, LeapDayPolicy};
+    use anglican_calendar::year_calendar::Year;
     use chrono::NaiveDate;
-    let base = NaiveDate::from_ymd(2019, 6, 15);
-    for (wd, d) in vec![
-        (chrono::Weekday::Sun, 16),
-        (chrono::Weekday::Sat, 22),
-        (chrono::Weekday::Mon, 17),
-    ] {
-        let act = anglican_calendar::year_calendar::Year::next_exclusive(base, wd);
-        let exp = NaiveDate::from_ymd(2019, 6, d);
-        assert_eq!(exp, act, "exp {:?} act {:?}", exp, act);
-    }
+    let year = Year::new(2021);
+    let date = |dc| year.church_year_date(&dc, LeapDayPolicy::February28).unwrap();
+    assert_eq!(
+        Some(NaiveDate::from_ymd(2020, 12, 25)),
+        date(DateCal::Fixed { month: 12, day: 25 })
+    );
+    assert_eq!(Some(NaiveDate::from_ymd(2020, 11, 29)), date(DateCal::AdventNext));
+    assert_eq!(Some(NaiveDate::from_ymd(2021, 4, 4)), date(DateCal::Easter));
     ```
-                         */
-    pub fn next_exclusive(orig_date: NaiveDate, weekday: chrono::Weekday) -> NaiveDate {
-        let orig_dow = orig_date.weekday().num_days_from_sunday() as i8; /* Sun = 0 etc */
-        let req_dow = weekday.num_days_from_sunday() as i8; /* Sun = 0 etc */
-        let offset = i64::from(req_dow - orig_dow + if req_dow <= orig_dow { 7 } else { 0 });
-        orig_date + Duration::days(offset)
+    */
+    pub fn church_year_date(
+        &self,
+        date_cal: &calendar::DateCal,
+        leap_day: calendar::LeapDayPolicy,
+    ) -> Result<Option<NaiveDate>, calendar::CalendarError> {
+        let date = self.date_cal_to_date(date_cal, leap_day)?;
+        if date >= self.advent_previous && date < self.advent_next {
+            return Ok(Some(date));
+        }
+        let previous_date = self
+            .other(self.ad - 1)
+            .date_cal_to_date(date_cal, leap_day)?;
+        if previous_date >= self.advent_previous && previous_date < self.advent_next {
+            Ok(Some(previous_date))
+        } else {
+            Ok(None)
+        }
     }
-    /** the next day being the specified weekday, including the original date.
+    /** The name of the Church year that ends in this year, including
+    the Sunday lectionary year e.g. "Year B 2020–21". */
+    pub fn church_year_name(&self) -> String {
+        format!(
+            "Year {} {}–{:02}",
+            self.church_year_lectionary().sunday,
+            self.ad - 1,
+            self.ad.rem_euclid(100)
+        )
+    }
+    /** The lectionary years for the Church year that ends in this
+    year, so from the previous Advent Sunday.
 
     ```
-    use chrono::NaiveDate;
-    let base = NaiveDate::from_ymd(2019, 6, 15);
-    for (wd, d) in vec![
-        (chrono::Weekday::Sun, 16),
-        (chrono::Weekday::Sat, 15),
-        (chrono::Weekday::Mon, 17),
-    ] {
-        let act = anglican_calendar::year_calendar::Year::next_inclusive(base, wd);
-        let exp = NaiveDate::from_ymd(2019, 6, d);
-        assert_eq!(exp, act, "exp {:?} act {:?}", exp, act);
-    }
+    use anglican_calendar::year_calendar::{Lectionary, SundayCycle, Year};
+    assert_eq!(
+        Lectionary {
+            sunday: SundayCycle::B,
+            weekday: 1,
+            daily_office: 1,
+        },
+        Year::new(2021).church_year_lectionary()
+    );
+    assert_eq!(
+        "Year A, Weekday Year 2, Daily Office Year 2",
+        Year::new(2020).church_year_lectionary().to_string()
+    );
     ```
-                         */
-    pub fn next_inclusive(orig_date: NaiveDate, weekday: chrono::Weekday) -> NaiveDate {
-        let orig_dow = orig_date.weekday().num_days_from_sunday() as i8; /* Sun = 0 etc */
-        let req_dow = weekday.num_days_from_sunday() as i8; /* Sun = 0 etc */
-        let offset = i64::from(req_dow - orig_dow + if req_dow < orig_dow { 7 } else { 0 });
-        orig_date + Duration::days(offset)
+    */
+    pub fn church_year_lectionary(&self) -> Lectionary {
+        let sunday = match self.ad.rem_euclid(3) {
+            1 => SundayCycle::A,
+            2 => SundayCycle::B,
+            _ => SundayCycle::C,
+        };
+        /* Year 1 is the Church year ending in an odd-numbered year */
+        let year_1_or_2 = if self.ad.rem_euclid(2) == 1 { 1 } else { 2 };
+        Lectionary {
+            sunday,
+            weekday: year_1_or_2,
+            daily_office: year_1_or_2,
+        }
     }
-    /** the most recent day being the specified weekday, excluding the original date.
+    /** The lectionary years for a date, which change on Advent Sunday.
 
     ```
+    use anglican_calendar::year_calendar::{SundayCycle, Year};
     use chrono::NaiveDate;
-    let base = NaiveDate::from_ymd(2019, 6, 15);
-    for (wd, d) in vec![
-        (chrono::Weekday::Sun, 9),
-        (chrono::Weekday::Sat, 8),
-        (chrono::Weekday::Mon, 10),
-    ] {
-        let act = anglican_calendar::year_calendar::Year::previous_exclusive(base, wd);
-        let exp = NaiveDate::from_ymd(2019, 6, d);
-        assert_eq!(exp, act, "exp {:?} act {:?}", exp, act);
-    }
+    let year = Year::new(2021);
+    assert_eq!(SundayCycle::B, year.lectionary(NaiveDate::from_ymd(2021, 11, 27)).sunday);
+    assert_eq!(SundayCycle::C, year.lectionary(NaiveDate::from_ymd(2021, 11, 28)).sunday);
+    assert_eq!(2, year.lectionary(NaiveDate::from_ymd(2021, 11, 28)).daily_office);
     ```
-                         */
-    pub fn previous_exclusive(orig_date: NaiveDate, weekday: chrono::Weekday) -> NaiveDate {
-        let orig_dow = orig_date.weekday().num_days_from_sunday() as i8; /* Sun = 0 etc */
-        let req_dow = weekday.num_days_from_sunday() as i8; /* Sun = 0 etc */
-        let offset = i64::from(req_dow - orig_dow + if req_dow < orig_dow { 0 } else { -7 });
-        orig_date + Duration::days(offset)
+    */
+    pub fn lectionary(&self, date: NaiveDate) -> Lectionary {
+        if date >= self.advent_next {
+            self.other(self.ad + 1).church_year_lectionary()
+        } else if date < self.advent_previous {
+            self.other(self.ad - 1).church_year_lectionary()
+        } else {
+            self.church_year_lectionary()
+        }
     }
-    /** the most recent day being the specified weekday, including the original date.
+    /** whether a Sunday on this date takes precedence over a festival
+    i.e. the date is in Advent, or from Ash Wednesday to Pentecost */
+    fn sunday_has_precedence(&self, date: NaiveDate) -> bool {
+        self.is_in_advent(date) || (date >= self.ash_wednesday && date <= self.pentecost)
+    }
+    /** whether a date is in Advent, from either Advent Sunday of the
+    year to Christmas Eve */
+    fn is_in_advent(&self, date: NaiveDate) -> bool {
+        (date >= self.advent_next && date < Year::ymd(self.ad, 12, 25))
+            || (date >= self.advent_previous && date < Year::ymd(self.ad - 1, 12, 25))
+    }
+    /** a date that is in every year e.g. 25 December */
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("a date in every year")
+    }
+    /** The Ember Days in this calendar year: the Wednesday, Friday and
+    Saturday after the first Sunday of Lent, the Day of Pentecost, Holy
+    Cross Day and St Lucy's Day.
+
+    ```
+    use anglican_calendar::year_calendar::Year;
+    use chrono::NaiveDate;
+    let ember_days: Vec<(u32, u32)> = vec![
+        (2, 24), (2, 26), (2, 27),
+        (5, 26), (5, 28), (5, 29),
+        (9, 15), (9, 17), (9, 18),
+        (12, 15), (12, 17), (12, 18),
+    ];
+    let exp: Vec<NaiveDate> = ember_days
+        .iter()
+        .map(|(m, d)| NaiveDate::from_ymd(2021, *m, *d))
+        .collect();
+    assert_eq!(exp, Year::new(2021).ember_days());
+    ```
+    */
+    pub fn ember_days(&self) -> Vec<NaiveDate> {
+        let mut days = vec![];
+        for d in &[
+            self.ash_wednesday + Duration::days(4),
+            self.pentecost,
+            Year::ymd(self.ad, 9, 14),
+            Year::ymd(self.ad, 12, 13),
+        ] {
+            let wednesday = Year::next_exclusive(*d, chrono::Weekday::Wed);
+            days.push(wednesday);
+            days.push(wednesday + Duration::days(2));
+            days.push(wednesday + Duration::days(3));
+        }
+        days
+    }
+    /** The Rogation Days: the Monday, Tuesday and Wednesday before
+    Ascension Day.
+
+    ```
+    use anglican_calendar::year_calendar::Year;
+    use chrono::NaiveDate;
+    assert_eq!(
+        vec![
+            NaiveDate::from_ymd(2021, 5, 10),
+            NaiveDate::from_ymd(2021, 5, 11),
+            NaiveDate::from_ymd(2021, 5, 12),
+        ],
+        Year::new(2021).rogation_days()
+    );
+    ```
+    */
+    pub fn rogation_days(&self) -> Vec<NaiveDate> {
+        (36..=38)
+            .map(|rel| self.easter + Duration::days(rel))
+            .collect()
+    }
+    /** The name of a Sunday using the specified scheme e.g. "3rd Sunday of
+    Epiphany", "Proper 12" or "Septuagesima".
+
+    ```
+    use anglican_calendar::calendar::SundayScheme;
+    use anglican_calendar::year_calendar::Year;
+    use chrono::NaiveDate;
+    let year = Year::new(2021);
+    assert_eq!(
+        "3rd Sunday of Epiphany",
+        year.sunday_name(NaiveDate::from_ymd(2021, 1, 24), SundayScheme::CommonWorship)
+    );
+    assert_eq!(
+        "Proper 12",
+        year.sunday_name(
+            NaiveDate::from_ymd(2021, 7, 25),
+            SundayScheme::RevisedCommonLectionary
+        )
+    );
+    assert_eq!(
+        "Septuagesima",
+        year.sunday_name(
+            NaiveDate::from_ymd(2021, 1, 31),
+            SundayScheme::BookOfCommonPrayer
+        )
+    );
+    ```
+    */
+    pub fn sunday_name(&self, date: NaiveDate, scheme: calendar::SundayScheme) -> String {
+        self.sunday(date, scheme).1
+    }
+    /** The tag and the name of a Sunday using the specified scheme
+    e.g. ("3rd sunday of epiphany", "3rd Sunday of Epiphany"). The tag
+    is given separately so that it does not change if the name is
+    reworded.
+
+    ```
+    use anglican_calendar::calendar::SundayScheme;
+    use anglican_calendar::year_calendar::Year;
+    use chrono::NaiveDate;
+    let year = Year::new(2021);
+    assert_eq!(
+        (
+            "sunday next before lent".to_string(),
+            "Sunday next before Lent".to_string()
+        ),
+        year.sunday(NaiveDate::from_ymd(2021, 2, 14), SundayScheme::CommonWorship)
+    );
+    ```
+    */
+    pub fn sunday(&self, date: NaiveDate, scheme: calendar::SundayScheme) -> (String, String) {
+        use calendar::SundayScheme::*;
+        /* use the Church year that contains the date */
+        if date >= self.advent_next {
+            return self.other(self.ad + 1).sunday(date, scheme);
+        }
+        if date < self.advent_previous {
+            return self.other(self.ad - 1).sunday(date, scheme);
+        }
+        let named = |tag: &str, title: &str| (tag.to_string(), title.to_string());
+        let weeks = |from: NaiveDate, to: NaiveDate| (to - from).num_days() / 7;
+        let christmas = Year::ymd(self.ad - 1, 12, 25);
+        let epiphany = Year::ymd(self.ad, 1, 6);
+        let lent_1 = self.ash_wednesday + Duration::days(4);
+        let trinity = self.pentecost + Duration::days(7);
+        if date < christmas {
+            let n = ordinal(weeks(self.advent_previous, date) + 1);
+            match scheme {
+                BookOfCommonPrayer => (
+                    format!("{} sunday in advent", n),
+                    format!("{} Sunday in Advent", n),
+                ),
+                _ => (
+                    format!("{} sunday of advent", n),
+                    format!("{} Sunday of Advent", n),
+                ),
+            }
+        } else if date == christmas {
+            named("christmas day", "Christmas Day")
+        } else if date < epiphany {
+            let n = ordinal(weeks(christmas + Duration::days(1), date) + 1);
+            match scheme {
+                CommonWorship => (
+                    format!("{} sunday of christmas", n),
+                    format!("{} Sunday of Christmas", n),
+                ),
+                _ => (
+                    format!("{} sunday after christmas day", n),
+                    format!("{} Sunday after Christmas Day", n),
+                ),
+            }
+        } else if date == epiphany {
+            named("the epiphany", "The Epiphany")
+        } else if date < lent_1 {
+            let after_epiphany = weeks(epiphany + Duration::days(1), date) + 1;
+            let before_lent = weeks(date, lent_1);
+            match scheme {
+                CommonWorship => {
+                    if before_lent == 1 {
+                        named("sunday next before lent", "Sunday next before Lent")
+                    } else if date <= Year::ymd(self.ad, 2, 2) {
+                        if after_epiphany == 1 {
+                            named("the baptism of christ", "The Baptism of Christ")
+                        } else {
+                            let n = ordinal(after_epiphany);
+                            (
+                                format!("{} sunday of epiphany", n),
+                                format!("{} Sunday of Epiphany", n),
+                            )
+                        }
+                    } else {
+                        let n = ordinal(before_lent);
+                        (
+                            format!("{} sunday before lent", n),
+                            format!("{} Sunday before Lent", n),
+                        )
+                    }
+                }
+                RevisedCommonLectionary => {
+                    if before_lent == 1 {
+                        named(
+                            "last sunday after the epiphany",
+                            "Last Sunday after the Epiphany",
+                        )
+                    } else if after_epiphany == 1 {
+                        named(
+                            "1st sunday after the epiphany: the baptism of our lord",
+                            "1st Sunday after the Epiphany: The Baptism of Our Lord",
+                        )
+                    } else {
+                        let n = ordinal(after_epiphany);
+                        (
+                            format!("{} sunday after the epiphany", n),
+                            format!("{} Sunday after the Epiphany", n),
+                        )
+                    }
+                }
+                BookOfCommonPrayer => match before_lent {
+                    3 => named("septuagesima", "Septuagesima"),
+                    2 => named("sexagesima", "Sexagesima"),
+                    1 => named("quinquagesima", "Quinquagesima"),
+                    _ => {
+                        let n = ordinal(after_epiphany);
+                        (
+                            format!("{} sunday after the epiphany", n),
+                            format!("{} Sunday after the Epiphany", n),
+                        )
+                    }
+                },
+            }
+        } else if date < self.palm_sunday {
+            let n = ordinal(weeks(lent_1, date) + 1);
+            match scheme {
+                CommonWorship => (
+                    format!("{} sunday of lent", n),
+                    format!("{} Sunday of Lent", n),
+                ),
+                _ => (
+                    format!("{} sunday in lent", n),
+                    format!("{} Sunday in Lent", n),
+                ),
+            }
+        } else if date == self.palm_sunday {
+            match scheme {
+                BookOfCommonPrayer => {
+                    named("sunday next before easter", "Sunday next before Easter")
+                }
+                _ => named("palm sunday", "Palm Sunday"),
+            }
+        } else if date == self.easter {
+            named("easter day", "Easter Day")
+        } else if date < self.pentecost {
+            let n = weeks(self.easter, date);
+            match scheme {
+                BookOfCommonPrayer => {
+                    if n == 6 {
+                        named("sunday after ascension day", "Sunday after Ascension Day")
+                    } else {
+                        (
+                            format!("{} sunday after easter", ordinal(n)),
+                            format!("{} Sunday after Easter", ordinal(n)),
+                        )
+                    }
+                }
+                _ => (
+                    format!("{} sunday of easter", ordinal(n + 1)),
+                    format!("{} Sunday of Easter", ordinal(n + 1)),
+                ),
+            }
+        } else if date == self.pentecost {
+            match scheme {
+                BookOfCommonPrayer => named("whitsunday", "Whitsunday"),
+                _ => named("day of pentecost", "Day of Pentecost"),
+            }
+        } else if date == trinity {
+            named("trinity sunday", "Trinity Sunday")
+        } else {
+            let before_advent = weeks(date, self.advent_next);
+            let after_trinity = ordinal(weeks(trinity, date));
+            match scheme {
+                CommonWorship => match before_advent {
+                    1 => named("christ the king", "Christ the King"),
+                    2..=4 => {
+                        let n = ordinal(before_advent);
+                        (
+                            format!("{} sunday before advent", n),
+                            format!("{} Sunday before Advent", n),
+                        )
+                    }
+                    _ => (
+                        format!("{} sunday after trinity", after_trinity),
+                        format!("{} Sunday after Trinity", after_trinity),
+                    ),
+                },
+                RevisedCommonLectionary => {
+                    if before_advent == 1 {
+                        named(
+                            "last sunday after pentecost: christ the king",
+                            "Last Sunday after Pentecost: Christ the King",
+                        )
+                    } else {
+                        /* Proper 1 is the Sunday between 8 and 14 May */
+                        let proper_1 = Year::ymd(date.year(), 5, 8);
+                        let n = weeks(proper_1, date) + 1;
+                        (format!("proper {}", n), format!("Proper {}", n))
+                    }
+                }
+                BookOfCommonPrayer => {
+                    if before_advent == 1 {
+                        named("sunday next before advent", "Sunday next before Advent")
+                    } else {
+                        (
+                            format!("{} sunday after trinity", after_trinity),
+                            format!("{} Sunday after Trinity", after_trinity),
+                        )
+                    }
+                }
+            }
+        }
+    }
+    /** Calculate the date of Easter Day. Returns result as number of days since March 0.
+
+    Uses Michael Behrend's version of Clavius’s original method, see
+    www.cantab.net/users/michael.behrend/algorithms/easter/pages/main.html */
+    pub fn computus(year: i32) -> i32 {
+        let c = year / 100;
+        let d = (3 * c - 5) / 4;
+        // (solar correction) + 10, also used for day of week
+        let e = (8 * c + 13) / 25; // (lunar correction) + 5
+        let f = year % 19; // (golden number of year) - 1
+                           // Get q, where q = 53 - (Clavius epact), so that
+                           //    q + 21 = date of Paschal full moon in days since March 0.
+                           // Value on left of % is always >= 0, so no worry there.
+        let mut q = (227 - 11 * f + d - e) % 30;
+        if (q == 29) || ((q == 28) && (f >= 11)) {
+            q -= 1;
+        }
+        // Get day of week of Paschal full moon (0 = Sun, 1 = Mon, ..., 6 = Sat)
+        let w = (year + (year / 4) - d + q) % 7;
+        // Get next Sunday strictly after Paschal full moon
+        q + 28 - w
+    }
+    /** Calculate the date of Orthodox Easter Day (Pascha). Returns
+    result as number of days since March 0 in the Gregorian calendar.
+
+    Uses Meeus's Julian algorithm, then converts the Julian date to
+    the Gregorian calendar.
+
+    ```
+    use anglican_calendar::year_calendar::Year;
+    assert_eq!(59, Year::computus_julian(2019)); // 28 April
+    assert_eq!(63, Year::computus_julian(2021)); // 2 May
+    assert_eq!(47, Year::computus_julian(2023)); // 16 April
+    assert_eq!(51, Year::computus_julian(2025)); // 20 April
+    ```
+    */
+    pub fn computus_julian(year: i32) -> i32 {
+        let a = year % 4;
+        let b = year % 7;
+        let c = year % 19;
+        let d = (19 * c + 15) % 30;
+        let e = (2 * a + 4 * b - d + 34) % 7;
+        // days between the Julian and Gregorian calendars
+        let julian_offset = year / 100 - year / 400 - 2;
+        22 + d + e + julian_offset
+    }
+    /** The date of Easter Day using the specified reckoning, whatever
+    the reckoning of the year. */
+    pub fn easter_by(&self, reckoning: calendar::EasterReckoning) -> NaiveDate {
+        if reckoning == self.reckoning {
+            self.easter
+        } else {
+            Year::with_reckoning(self.ad, reckoning).easter
+        }
+    }
+    /** another year, with the same reckoning of Easter */
+    pub fn other(&self, ad: i32) -> Year {
+        Year::with_reckoning(ad, self.reckoning)
+    }
+    /** the next day being the specified weekday, not including the original date.
+
+    ```
+    use chrono::NaiveDate;
+    let base = NaiveDate::from_ymd(2019, 6, 15);
+    for (wd, d) in vec![
+        (chrono::Weekday::Sun, 16),
+        (chrono::Weekday::Sat, 22),
+        (chrono::Weekday::Mon, 17),
+    ] {
+        let act = anglican_calendar::year_calendar::Year::next_exclusive(base, wd);
+        let exp = NaiveDate::from_ymd(2019, 6, d);
+        assert_eq!(exp, act, "exp {:?} act {:?}", exp, act);
+    }
+    ```
+                         */
+    pub fn next_exclusive(orig_date: NaiveDate, weekday: chrono::Weekday) -> NaiveDate {
+        let orig_dow = orig_date.weekday().num_days_from_sunday() as i8; /* Sun = 0 etc */
+        let req_dow = weekday.num_days_from_sunday() as i8; /* Sun = 0 etc */
+        let offset = i64::from(req_dow - orig_dow + if req_dow <= orig_dow { 7 } else { 0 });
+        orig_date + Duration::days(offset)
+    }
+    /** the next day being the specified weekday, including the original date.
+
+    ```
+    use chrono::NaiveDate;
+    let base = NaiveDate::from_ymd(2019, 6, 15);
+    for (wd, d) in vec![
+        (chrono::Weekday::Sun, 16),
+        (chrono::Weekday::Sat, 15),
+        (chrono::Weekday::Mon, 17),
+    ] {
+        let act = anglican_calendar::year_calendar::Year::next_inclusive(base, wd);
+        let exp = NaiveDate::from_ymd(2019, 6, d);
+        assert_eq!(exp, act, "exp {:?} act {:?}", exp, act);
+    }
+    ```
+                         */
+    pub fn next_inclusive(orig_date: NaiveDate, weekday: chrono::Weekday) -> NaiveDate {
+        let orig_dow = orig_date.weekday().num_days_from_sunday() as i8; /* Sun = 0 etc */
+        let req_dow = weekday.num_days_from_sunday() as i8; /* Sun = 0 etc */
+        let offset = i64::from(req_dow - orig_dow + if req_dow < orig_dow { 7 } else { 0 });
+        orig_date + Duration::days(offset)
+    }
+    /** the most recent day being the specified weekday, excluding the original date.
+
+    ```
+    use chrono::NaiveDate;
+    let base = NaiveDate::from_ymd(2019, 6, 15);
+    for (wd, d) in vec![
+        (chrono::Weekday::Sun, 9),
+        (chrono::Weekday::Sat, 8),
+        (chrono::Weekday::Mon, 10),
+    ] {
+        let act = anglican_calendar::year_calendar::Year::previous_exclusive(base, wd);
+        let exp = NaiveDate::from_ymd(2019, 6, d);
+        assert_eq!(exp, act, "exp {:?} act {:?}", exp, act);
+    }
+    ```
+                         */
+    pub fn previous_exclusive(orig_date: NaiveDate, weekday: chrono::Weekday) -> NaiveDate {
+        let orig_dow = orig_date.weekday().num_days_from_sunday() as i8; /* Sun = 0 etc */
+        let req_dow = weekday.num_days_from_sunday() as i8; /* Sun = 0 etc */
+        let offset = i64::from(req_dow - orig_dow + if req_dow < orig_dow { 0 } else { -7 });
+        orig_date + Duration::days(offset)
+    }
+    /** the most recent day being the specified weekday, including the original date.
 
     ```
     use chrono::NaiveDate;
@@ -694,47 +2741,200 @@ impl Year {
         let offset = i64::from(req_dow - orig_dow + if req_dow <= orig_dow { 0 } else { -7 });
         orig_date + Duration::days(offset)
     }
-    /** The seasonal colour for a date. See [CoE Common Worship](https://www.churchofengland.org/prayer-and-worship/worship-texts-and-resources/common-worship/prayer-and-worship/worship-texts-and-resources/common-worship/churchs-year/rules).
-    "White is the colour for the festal periods from Christmas
-       Day to the Presentation and from Easter Day to the Eve of
-       Pentecost, for Trinity Sunday, for Festivals of Our Lord and
-       the Blessed Virgin Mary, for All Saints’ Day, and for the
-       Festivals of those saints not venerated as martyrs, for the
-       Feast of Dedication of a church, at Holy Communion on Maundy
-       Thursday and in thanksgiving for Holy Communion and Holy
-       Baptism..." */
-    pub fn season_colour(&self, date: NaiveDate) -> calendar::SeasonColour {
-        if date >= NaiveDate::from_ymd(self.ad, 12, 25)
-            || date <= NaiveDate::from_ymd(self.ad, 2, 2)
-            || (date >= self.easter && date < self.pentecost)
-            || date == NaiveDate::from_ymd(self.ad, 11, 1)
-        // TODO  for Trinity Sunday, for Festivals of Our Lord and the Blessed Virgin Mary,
-        {
-            calendar::SeasonColour::White
-        }
-        /* "Red is used during Holy Week (except at Holy Communion on
-         * Maundy Thursday), on the Feast of Pentecost... Coloured
-         * hangings are traditionally removed for Good Friday and
-         * Easter Eve, but red is the colour for the liturgy on Good
-         * Friday..." */
-        else if (date >= self.palm_sunday && date < self.easter) || date == self.pentecost {
-            calendar::SeasonColour::Red
-        }
-        /* "Purple ... is the colour for Advent and from Ash Wednesday
-        until the day before Palm Sunday..." */
-        else if (date >= self.advent_next && date < NaiveDate::from_ymd(self.ad, 12, 25))
-            || (date >= self.ash_wednesday && date < self.palm_sunday)
+    /** the nearest day being the specified weekday, which may be up to
+    three days before or after the original date.
+
+    ```
+    use chrono::NaiveDate;
+    let base = NaiveDate::from_ymd(2019, 6, 15);
+    for (wd, d) in vec![
+        (chrono::Weekday::Sun, 16),
+        (chrono::Weekday::Sat, 15),
+        (chrono::Weekday::Wed, 12),
+        (chrono::Weekday::Tue, 18),
+    ] {
+        let act = anglican_calendar::year_calendar::Year::nearest(base, wd);
+        let exp = NaiveDate::from_ymd(2019, 6, d);
+        assert_eq!(exp, act, "exp {:?} act {:?}", exp, act);
+    }
+    ```
+                         */
+    pub fn nearest(orig_date: NaiveDate, weekday: chrono::Weekday) -> NaiveDate {
+        let next = Year::next_inclusive(orig_date, weekday);
+        if next - orig_date <= Duration::days(3) {
+            next
+        } else {
+            next - Duration::days(7)
+        }
+    }
+    /** the nearest day being the specified weekday, excluding the
+    original date; if the original date is that weekday, the same
+    weekday in the following week.
+
+    ```
+    use chrono::NaiveDate;
+    let base = NaiveDate::from_ymd(2019, 6, 15);
+    for (wd, d) in vec![
+        (chrono::Weekday::Sun, 16),
+        (chrono::Weekday::Sat, 22),
+        (chrono::Weekday::Wed, 12),
+    ] {
+        let act = anglican_calendar::year_calendar::Year::nearest_exclusive(base, wd);
+        let exp = NaiveDate::from_ymd(2019, 6, d);
+        assert_eq!(exp, act, "exp {:?} act {:?}", exp, act);
+    }
+    ```
+                         */
+    pub fn nearest_exclusive(orig_date: NaiveDate, weekday: chrono::Weekday) -> NaiveDate {
+        if orig_date.weekday() == weekday {
+            orig_date + Duration::days(7)
+        } else {
+            Year::nearest(orig_date, weekday)
+        }
+    }
+    /** The season of the Church year for a date. The Epiphany season
+    runs until the Presentation (2 February), as in Common Worship.
+
+    ```
+    use anglican_calendar::calendar::Season;
+    use anglican_calendar::year_calendar::Year;
+    use chrono::NaiveDate;
+    let year = Year::new(2021);
+    assert_eq!(Season::Advent, year.season(NaiveDate::from_ymd(2020, 12, 24)));
+    assert_eq!(Season::Christmas, year.season(NaiveDate::from_ymd(2021, 1, 5)));
+    assert_eq!(Season::Epiphany, year.season(NaiveDate::from_ymd(2021, 2, 2)));
+    assert_eq!(Season::Ordinary, year.season(NaiveDate::from_ymd(2021, 2, 3)));
+    assert_eq!(Season::Lent, year.season(NaiveDate::from_ymd(2021, 4, 3)));
+    assert_eq!(Season::Easter, year.season(NaiveDate::from_ymd(2021, 5, 23)));
+    assert_eq!(Season::Ordinary, year.season(NaiveDate::from_ymd(2021, 5, 24)));
+    ```
+    */
+    pub fn season(&self, date: NaiveDate) -> calendar::Season {
+        if date.year() != self.ad {
+            return self.other(date.year()).season(date);
+        }
+        let christmas = Year::ymd(self.ad, 12, 25);
+        if date >= christmas || date < Year::ymd(self.ad, 1, 6) {
+            calendar::Season::Christmas
+        } else if date >= self.advent_next {
+            calendar::Season::Advent
+        } else if date <= Year::ymd(self.ad, 2, 2) {
+            calendar::Season::Epiphany
+        } else if date >= self.ash_wednesday && date < self.easter {
+            calendar::Season::Lent
+        } else if date >= self.easter && date <= self.pentecost {
+            calendar::Season::Easter
+        } else {
+            calendar::Season::Ordinary
+        }
+    }
+    /** The part of the season for a date, if any. Which sub-seasons
+    are kept depends on the [calendar::SundayScheme] of the province.
+
+    ```
+    use anglican_calendar::calendar::{SubSeason, SundayScheme};
+    use anglican_calendar::year_calendar::Year;
+    use chrono::NaiveDate;
+    let year = Year::new(2021);
+    let sub_season = |m, d, scheme| year.sub_season(NaiveDate::from_ymd(2021, m, d), scheme);
+    use SundayScheme::*;
+    assert_eq!(Some(SubSeason::HolyWeek), sub_season(3, 28, CommonWorship));
+    assert_eq!(Some(SubSeason::EasterOctave), sub_season(4, 11, RevisedCommonLectionary));
+    assert_eq!(Some(SubSeason::PreLent), sub_season(1, 31, BookOfCommonPrayer));
+    assert_eq!(None, sub_season(1, 31, CommonWorship));
+    assert_eq!(Some(SubSeason::Trinity), sub_season(10, 31, CommonWorship));
+    assert_eq!(Some(SubSeason::Kingdom), sub_season(11, 1, CommonWorship));
+    assert_eq!(Some(SubSeason::Trinity), sub_season(11, 1, BookOfCommonPrayer));
+    assert_eq!(None, sub_season(11, 1, RevisedCommonLectionary));
+    ```
+    */
+    pub fn sub_season(
+        &self,
+        date: NaiveDate,
+        scheme: calendar::SundayScheme,
+    ) -> Option<calendar::SubSeason> {
+        use calendar::SundayScheme::*;
+        if date.year() != self.ad {
+            return self.other(date.year()).sub_season(date, scheme);
+        }
+        let trinity = self.pentecost + Duration::days(7);
+        let all_saints = Year::ymd(self.ad, 11, 1);
+        if date >= self.palm_sunday && date < self.easter {
+            Some(calendar::SubSeason::HolyWeek)
+        } else if date >= self.easter && date < self.easter_sunday_2 + Duration::days(1) {
+            Some(calendar::SubSeason::EasterOctave)
+        } else if scheme == BookOfCommonPrayer
+            && date >= self.easter - Duration::weeks(9)
+            && date < self.ash_wednesday
         {
-            calendar::SeasonColour::Purple
+            Some(calendar::SubSeason::PreLent)
+        } else if scheme == CommonWorship && date >= all_saints && date < self.advent_next {
+            Some(calendar::SubSeason::Kingdom)
+        } else if scheme != RevisedCommonLectionary && date >= trinity && date < self.advent_next {
+            Some(calendar::SubSeason::Trinity)
+        } else {
+            None
         }
-        /* "Green is used from the day after the Presentation until
-        Shrove Tuesday, and from the day after Pentecost until the
-        eve of All Saints’ Day, except when other provision is
-        made..." */
-        else {
-            calendar::SeasonColour::Green
+    }
+    /** The season and sub-season of a date e.g. "Lent, Holy Week" */
+    pub fn season_name(&self, date: NaiveDate, scheme: calendar::SundayScheme) -> String {
+        match self.sub_season(date, scheme) {
+            Some(sub_season) => format!("{}, {}", self.season(date), sub_season),
+            None => self.season(date).to_string(),
         }
     }
+    /** The seasonal colour for a date, using the Common Worship rules
+    (see [colours::ColourRules] for the rules of other provinces).
+    Returns an error if the built-in rules cannot be read. */
+    pub fn season_colour(
+        &self,
+        date: NaiveDate,
+    ) -> Result<calendar::SeasonColour, calendar::CalendarError> {
+        Ok(colours::ColourRules::common_worship()?.season_colour(self, date))
+    }
+}
+/** The three-year Sunday cycle of the Revised Common Lectionary (and
+Common Worship), which starts on Advent Sunday */
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+pub enum SundayCycle {
+    A,
+    B,
+    C,
+}
+impl fmt::Display for SundayCycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+/** The lectionary years in use for a Church year */
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Lectionary {
+    /** the Sunday year (A, B or C) */
+    pub sunday: SundayCycle,
+    /** the weekday year (1 or 2) of the eucharistic lectionary */
+    pub weekday: u8,
+    /** the year (1 or 2) of the Daily Office lectionary (CW, ECUSA) */
+    pub daily_office: u8,
+}
+impl fmt::Display for Lectionary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Year {}, Weekday Year {}, Daily Office Year {}",
+            self.sunday, self.weekday, self.daily_office
+        )
+    }
+}
+/** an ordinal number e.g. "1st", "22nd" */
+fn ordinal(n: i64) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
 }
 
 #[cfg(test)]
@@ -749,9 +2949,16 @@ mod tests {
         let day_holydays: Vec<YearHolyday> = vec![];
         let year = Year::new(2019);
         let mut ye =
-            YearHolyday::from_holyday(&calendar::HolydayRef::new(Holyday::default()), &year);
+            YearHolyday::from_holyday(&calendar::HolydayRef::new(Holyday::default()), &year)
+                .unwrap();
         let ye_exp = ye.clone();
-        let er = YearCalendar::fix_holyday_date_is_ok(&day_holydays, &mut ye, &year);
+        let er = YearCalendar::fix_holyday_date_is_ok(
+            &day_holydays,
+            &mut ye,
+            &year,
+            calendar::TransferScheme::CommonWorship,
+        )
+        .unwrap();
         assert_eq!(DropStatus::Keep, er);
         assert_eq!(ye_exp, ye, "bad holyday {:?}", ye);
     }
@@ -768,11 +2975,92 @@ mod tests {
             transfer: calendar::TransferType::Normal,
             ..Holyday::default()
         };
-        let mut ye = YearHolyday::from_holyday(&calendar::HolydayRef::new(holyday), &year);
-        let er = YearCalendar::fix_holyday_date_is_ok(&day_holydays, &mut ye, &year);
+        let mut ye = YearHolyday::from_holyday(&calendar::HolydayRef::new(holyday), &year).unwrap();
+        let er = YearCalendar::fix_holyday_date_is_ok(
+            &day_holydays,
+            &mut ye,
+            &year,
+            calendar::TransferScheme::CommonWorship,
+        )
+        .unwrap();
         assert_eq!(DropStatus::Keep, er);
         assert_eq!(NaiveDate::from_ymd(2019, 4, 21), ye.date);
     }
+    #[test]
+    fn test_easter_reckoning() {
+        let mut cal = calendar_of(&[
+            ("easter", calendar::HolydayClass::Principal, DateCal::Easter),
+            (
+                "pentecost",
+                calendar::HolydayClass::Principal,
+                DateCal::After {
+                    date: Box::new(DateCal::Easter),
+                    rel: 49,
+                },
+            ),
+            (
+                "pascha",
+                calendar::HolydayClass::Unclassified,
+                DateCal::OrthodoxEaster,
+            ),
+        ]);
+        let dates = |cal: &calendar::Calendar| -> Vec<NaiveDate> {
+            let ycal =
+                YearCalendar::from_calendar(cal, 2021, &YearCalendarOptions::default(), false)
+                    .unwrap();
+            ["easter", "pentecost", "pascha"]
+                .iter()
+                .map(|tag| ycal.find_by_tag(tag)[0].date())
+                .collect()
+        };
+        /* in 2021 the Western Easter is 4 April and the Orthodox Pascha
+        is 2 May */
+        assert_eq!(
+            vec![
+                NaiveDate::from_ymd(2021, 4, 4),
+                NaiveDate::from_ymd(2021, 5, 23),
+                NaiveDate::from_ymd(2021, 5, 2),
+            ],
+            dates(&cal)
+        );
+        cal.easter = calendar::EasterReckoning::Julian;
+        assert_eq!(
+            vec![
+                NaiveDate::from_ymd(2021, 5, 2),
+                NaiveDate::from_ymd(2021, 6, 20),
+                NaiveDate::from_ymd(2021, 5, 2),
+            ],
+            dates(&cal)
+        );
+        /* the OrthodoxEaster anchor, on its own and as the base of
+        another date */
+        let palm_sunday = DateCal::After {
+            date: Box::new(DateCal::OrthodoxEaster),
+            rel: -7,
+        };
+        for (year, pascha, palms) in [
+            (
+                2019,
+                NaiveDate::from_ymd(2019, 4, 28),
+                NaiveDate::from_ymd(2019, 4, 21),
+            ),
+            (
+                2020,
+                NaiveDate::from_ymd(2020, 4, 19),
+                NaiveDate::from_ymd(2020, 4, 12),
+            ),
+        ]
+        .iter()
+        {
+            let year = Year::new(*year);
+            let date = |dc: &DateCal| {
+                year.date_cal_to_date(dc, calendar::LeapDayPolicy::default())
+                    .unwrap()
+            };
+            assert_eq!(*pascha, date(&DateCal::OrthodoxEaster));
+            assert_eq!(*palms, date(&palm_sunday));
+        }
+    }
     // TODO add more test dates to cover all special cases
     #[test]
     fn test_dates_2019() {
@@ -878,6 +3166,1930 @@ mod tests {
         ];
         test_year(year, &tests);
     }
+    #[test]
+    fn test_church_year() {
+        let cal = calendar_of(&[
+            (
+                "christmas",
+                calendar::HolydayClass::Principal,
+                DateCal::Fixed { month: 12, day: 25 },
+            ),
+            (
+                "advent sunday",
+                calendar::HolydayClass::Principal,
+                DateCal::AdventNext,
+            ),
+            ("easter", calendar::HolydayClass::Principal, DateCal::Easter),
+            (
+                "andrew",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 11, day: 30 },
+            ),
+            (
+                "clement",
+                calendar::HolydayClass::Commemoration,
+                DateCal::Fixed { month: 11, day: 23 },
+            ),
+        ]);
+        let options = YearCalendarOptions {
+            church_year: true,
+            ..YearCalendarOptions::default()
+        };
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &options, false).unwrap();
+        let mut dates: Vec<(NaiveDate, String)> = ycal
+            .holydays_by_date
+            .iter()
+            .flat_map(|(d, yee)| yee.iter().map(move |ye| (*d, ye.holyday.tag())))
+            .collect();
+        dates.sort();
+        assert_eq!(
+            vec![
+                (
+                    NaiveDate::from_ymd(2020, 11, 29),
+                    "advent sunday".to_string()
+                ),
+                (NaiveDate::from_ymd(2020, 11, 30), "andrew".to_string()),
+                (NaiveDate::from_ymd(2020, 12, 25), "christmas".to_string()),
+                (NaiveDate::from_ymd(2021, 4, 4), "easter".to_string()),
+                (NaiveDate::from_ymd(2021, 11, 23), "clement".to_string()),
+            ],
+            dates
+        );
+        assert_eq!("Unknown Year B 2020–21", ycal.title());
+    }
+    #[test]
+    fn test_weekday_of_month() {
+        let thanksgiving: DateCal = ron::de::from_str(
+            "NthWeekdayOfMonth(month: 11, n: 4, day_of_week: (wd: \"Thursday\"))",
+        )
+        .unwrap();
+        let remembrance: DateCal =
+            ron::de::from_str("NthWeekdayOfMonth(month: 11, n: 2, day_of_week: (wd: \"Sunday\"))")
+                .unwrap();
+        let last_sunday_january: DateCal =
+            ron::de::from_str("LastWeekdayOfMonth(month: 1, day_of_week: (wd: \"Sunday\"))")
+                .unwrap();
+        let last_sunday_december = DateCal::LastWeekdayOfMonth {
+            month: 12,
+            day_of_week: calendar::OrderableDayOfWeek::from(chrono::Weekday::Sun),
+        };
+        for (year, dc, ed) in [
+            (2020, &thanksgiving, NaiveDate::from_ymd(2020, 11, 26)),
+            (2021, &thanksgiving, NaiveDate::from_ymd(2021, 11, 25)),
+            (2020, &remembrance, NaiveDate::from_ymd(2020, 11, 8)),
+            (2021, &remembrance, NaiveDate::from_ymd(2021, 11, 14)),
+            (2020, &last_sunday_january, NaiveDate::from_ymd(2020, 1, 26)),
+            (2021, &last_sunday_january, NaiveDate::from_ymd(2021, 1, 31)),
+            (
+                2020,
+                &last_sunday_december,
+                NaiveDate::from_ymd(2020, 12, 27),
+            ),
+            (
+                2022,
+                &last_sunday_december,
+                NaiveDate::from_ymd(2022, 12, 25),
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                *ed,
+                Year::new(*year)
+                    .date_cal_to_date(dc, calendar::LeapDayPolicy::default())
+                    .unwrap(),
+                "{:?}",
+                dc
+            );
+        }
+        let s = ron::ser::to_string(&thanksgiving).unwrap();
+        assert_eq!(thanksgiving, ron::de::from_str::<DateCal>(&s).unwrap());
+    }
+    #[test]
+    fn test_previous_and_nearest() {
+        let epiphany_sunday: DateCal = ron::de::from_str(
+            "Nearest(date: Fixed(month: 1, day: 5), day_of_week: (wd: \"Sunday\"))",
+        )
+        .unwrap();
+        let all_saints_sunday = DateCal::Nearest {
+            date: Box::new(DateCal::Fixed { month: 11, day: 2 }),
+            day_of_week: calendar::OrderableDayOfWeek::from(chrono::Weekday::Sun),
+            exclusive: false,
+        };
+        let sunday_near_epiphany_eve: DateCal = ron::de::from_str(
+            "Nearest(date: Fixed(month: 1, day: 5), day_of_week: (wd: \"Sunday\"), exclusive: true)",
+        )
+        .unwrap();
+        let sunday_next_before_advent: DateCal = ron::de::from_str(
+            "Previous(date: AdventNext, day_of_week: (wd: \"Sunday\"), exclusive: true)",
+        )
+        .unwrap();
+        let saturday_before_easter: DateCal =
+            ron::de::from_str("Previous(date: Easter, day_of_week: (wd: \"Saturday\"))").unwrap();
+        let sunday_on_or_before_easter = DateCal::Previous {
+            date: Box::new(DateCal::Easter),
+            day_of_week: calendar::OrderableDayOfWeek::from(chrono::Weekday::Sun),
+            exclusive: false,
+        };
+        for (year, dc, ed) in [
+            (2020, &epiphany_sunday, NaiveDate::from_ymd(2020, 1, 5)),
+            (2021, &epiphany_sunday, NaiveDate::from_ymd(2021, 1, 3)),
+            (2022, &epiphany_sunday, NaiveDate::from_ymd(2022, 1, 2)),
+            (2023, &epiphany_sunday, NaiveDate::from_ymd(2023, 1, 8)),
+            (2020, &all_saints_sunday, NaiveDate::from_ymd(2020, 11, 1)),
+            (2021, &all_saints_sunday, NaiveDate::from_ymd(2021, 10, 31)),
+            (2022, &all_saints_sunday, NaiveDate::from_ymd(2022, 10, 30)),
+            (2023, &all_saints_sunday, NaiveDate::from_ymd(2023, 11, 5)),
+            /* 5 January 2020 is itself a Sunday */
+            (
+                2020,
+                &sunday_near_epiphany_eve,
+                NaiveDate::from_ymd(2020, 1, 12),
+            ),
+            (
+                2021,
+                &sunday_near_epiphany_eve,
+                NaiveDate::from_ymd(2021, 1, 3),
+            ),
+            (
+                2020,
+                &sunday_next_before_advent,
+                NaiveDate::from_ymd(2020, 11, 22),
+            ),
+            (
+                2020,
+                &saturday_before_easter,
+                NaiveDate::from_ymd(2020, 4, 11),
+            ),
+            (
+                2020,
+                &sunday_on_or_before_easter,
+                NaiveDate::from_ymd(2020, 4, 12),
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                *ed,
+                Year::new(*year)
+                    .date_cal_to_date(dc, calendar::LeapDayPolicy::default())
+                    .unwrap(),
+                "{:?}",
+                dc
+            );
+        }
+    }
+    #[test]
+    fn test_sundays() {
+        let mut cal = calendar_of(&[
+            ("easter", calendar::HolydayClass::Principal, DateCal::Easter),
+            (
+                "mark",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 4, day: 25 },
+            ),
+            (
+                "james",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 7, day: 25 },
+            ),
+        ]);
+        cal.province = calendar::Province::ChurchOfEngland;
+        let options = YearCalendarOptions {
+            sundays: true,
+            ..YearCalendarOptions::default()
+        };
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &options, false).unwrap();
+        let on = |d: NaiveDate| -> Vec<String> {
+            let mut tags: Vec<String> = ycal
+                .holydays_by_date
+                .values()
+                .flatten()
+                .filter(|ye| ye.date == d)
+                .map(|ye| ye.holyday.tag())
+                .collect();
+            tags.sort();
+            tags
+        };
+        assert_eq!(vec!["easter"], on(NaiveDate::from_ymd(2021, 4, 4)));
+        assert_eq!(
+            vec!["4th sunday of easter"],
+            on(NaiveDate::from_ymd(2021, 4, 25))
+        );
+        assert_eq!(vec!["mark"], on(NaiveDate::from_ymd(2021, 4, 26)));
+        assert_eq!(vec!["james"], on(NaiveDate::from_ymd(2021, 7, 25)));
+        assert_eq!(
+            vec!["2nd sunday of christmas"],
+            on(NaiveDate::from_ymd(2021, 1, 3))
+        );
+        assert_eq!(
+            vec!["christ the king"],
+            on(NaiveDate::from_ymd(2021, 11, 21))
+        );
+        assert_eq!(
+            vec!["1st sunday of advent"],
+            on(NaiveDate::from_ymd(2021, 11, 28))
+        );
+        assert_eq!(
+            "21st Sunday after Trinity",
+            ycal.year.sunday_name(
+                NaiveDate::from_ymd(2021, 10, 24),
+                calendar::SundayScheme::CommonWorship
+            )
+        );
+        /* the named Sundays that the calendar already has are not
+        generated again */
+        let cofe =
+            calendar::Calendar::read(std::fs::File::open("data/final/cofe.data").unwrap()).unwrap();
+        let ycal = YearCalendar::from_calendar(&cofe, 2021, &options, false).unwrap();
+        for (tag, date) in [
+            ("trinity sunday", NaiveDate::from_ymd(2021, 5, 30)),
+            ("christ the king", NaiveDate::from_ymd(2021, 11, 21)),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                vec![*date],
+                ycal.find_by_tag(tag)
+                    .iter()
+                    .map(|ye| ye.date())
+                    .collect::<Vec<_>>(),
+                "{}",
+                tag
+            );
+            assert!(ycal.dropped_by_tag(tag).is_empty(), "{} dropped", tag);
+            assert_eq!(
+                vec![Outcome::Kept],
+                ycal.why(tag)
+                    .iter()
+                    .map(|d| d.outcome())
+                    .collect::<Vec<_>>()
+            );
+        }
+        assert!(ycal
+            .decisions()
+            .iter()
+            .all(|d| d.clash().map(|c| c.tag()) != Some(d.holyday.tag())));
+    }
+    #[test]
+    fn test_days_of_prayer() {
+        let mut cal = calendar_of(&[(
+            "pentecost",
+            calendar::HolydayClass::Principal,
+            DateCal::After {
+                date: Box::new(DateCal::Easter),
+                rel: 49,
+            },
+        )]);
+        let options = YearCalendarOptions::default();
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &options, false).unwrap();
+        assert_eq!(1, ycal.holydays_by_date.values().flatten().count());
+        cal.days_of_prayer = true;
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &options, false).unwrap();
+        let prayer: Vec<&YearHolyday> = ycal
+            .holydays_by_date
+            .values()
+            .flatten()
+            .filter(|ye| ye.holyday.class() == calendar::HolydayClass::DayOfPrayer)
+            .collect();
+        assert_eq!(15, prayer.len());
+        let ember_wednesday = &ycal.holydays_by_date[&NaiveDate::from_ymd(2021, 5, 26)];
+        assert_eq!(
+            "ember pentecost wednesday",
+            ember_wednesday[0].holyday.tag()
+        );
+        let options = YearCalendarOptions {
+            church_year: true,
+            ..YearCalendarOptions::default()
+        };
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &options, false).unwrap();
+        let lucy = &ycal.holydays_by_date[&NaiveDate::from_ymd(2020, 12, 16)];
+        assert_eq!("ember lucy wednesday", lucy[0].holyday.tag());
+        assert!(!ycal
+            .holydays_by_date
+            .contains_key(&NaiveDate::from_ymd(2021, 12, 15)));
+        for province in &["cofe", "ecusa", "bcp"] {
+            let edits = calendar::EdMods::read(
+                std::fs::File::open(format!("data/edits/{}.fixes", province)).unwrap(),
+            )
+            .unwrap();
+            let mut cal = calendar::Calendar::read(
+                std::fs::File::open(format!("data/final/{}.data", province)).unwrap(),
+            )
+            .unwrap();
+            assert!(cal.days_of_prayer, "{}", province);
+            cal.days_of_prayer = false;
+            cal.apply(&edits).unwrap();
+            assert!(cal.days_of_prayer, "{}", province);
+        }
+    }
+    #[test]
+    fn test_eves() {
+        let cal = calendar_from(
+            [
+                (
+                    "christmas",
+                    calendar::HolydayClass::Principal,
+                    DateCal::Fixed { month: 12, day: 25 },
+                ),
+                (
+                    "stephen",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 12, day: 26 },
+                ),
+                (
+                    "lent festival",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 3, day: 21 },
+                ),
+            ]
+            .iter()
+            .cloned()
+            .map(|(tag, class, date_cal)| Holyday {
+                has_eve: true,
+                ..holyday(tag, class, date_cal)
+            }),
+        );
+        let eves = |options: &YearCalendarOptions| -> Vec<(NaiveDate, String)> {
+            let ycal = YearCalendar::from_calendar(&cal, 2021, options, false).unwrap();
+            let mut eves: Vec<(NaiveDate, String)> = ycal
+                .holydays_by_date
+                .values()
+                .flatten()
+                .filter(|ye| ye.eve)
+                .map(|ye| (ye.date, ye.title()))
+                .collect();
+            eves.sort();
+            eves
+        };
+        assert!(eves(&YearCalendarOptions::default()).is_empty());
+        let options = YearCalendarOptions {
+            eves: true,
+            ..YearCalendarOptions::default()
+        };
+        assert_eq!(
+            vec![
+                (
+                    NaiveDate::from_ymd(2021, 3, 21),
+                    "Eve of lent festival".to_string()
+                ),
+                (
+                    NaiveDate::from_ymd(2021, 12, 24),
+                    "Eve of christmas".to_string()
+                ),
+            ],
+            eves(&options)
+        );
+    }
+    #[test]
+    fn test_date_errors() {
+        let cal = calendar_from(
+            [
+                (
+                    "leap",
+                    DateCal::Fixed { month: 2, day: 29 },
+                    calendar::LeapDayPolicy::March1,
+                ),
+                (
+                    "leap 28",
+                    DateCal::Fixed { month: 2, day: 29 },
+                    calendar::LeapDayPolicy::February28,
+                ),
+                (
+                    "bad day",
+                    DateCal::Fixed { month: 2, day: 30 },
+                    calendar::LeapDayPolicy::default(),
+                ),
+                (
+                    "fifth sunday",
+                    DateCal::NthWeekdayOfMonth {
+                        month: 2,
+                        n: 5,
+                        day_of_week: calendar::OrderableDayOfWeek::from(chrono::Weekday::Sun),
+                    },
+                    calendar::LeapDayPolicy::default(),
+                ),
+                (
+                    "after bad month",
+                    DateCal::After {
+                        date: Box::new(DateCal::Fixed { month: 13, day: 1 }),
+                        rel: 1,
+                    },
+                    calendar::LeapDayPolicy::default(),
+                ),
+            ]
+            .iter()
+            .cloned()
+            .map(|(tag, date_cal, leap_day)| Holyday {
+                leap_day,
+                transfer: TransferType::DoNotTransfer,
+                ..holyday(tag, calendar::HolydayClass::Commemoration, date_cal)
+            }),
+        );
+        let dates = |ycal: &YearCalendar| -> Vec<(String, NaiveDate)> {
+            let mut dates: Vec<(String, NaiveDate)> = ycal
+                .holydays_by_date
+                .values()
+                .flatten()
+                .map(|ye| (ye.holyday.tag(), ye.date))
+                .collect();
+            dates.sort();
+            dates
+        };
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &YearCalendarOptions::default(), false)
+            .unwrap();
+        assert_eq!(
+            vec![
+                ("leap".to_string(), NaiveDate::from_ymd(2021, 3, 1)),
+                ("leap 28".to_string(), NaiveDate::from_ymd(2021, 2, 28)),
+            ],
+            dates(&ycal)
+        );
+        assert_eq!(3, ycal.errors().len());
+        for tag in ["bad day", "fifth sunday", "after bad month"].iter() {
+            assert!(
+                ycal.errors()
+                    .iter()
+                    .any(|e| e.message().contains(&format!("of {} (", tag))),
+                "no error for {}",
+                tag
+            );
+        }
+        /* February 2032 has five Sundays */
+        let ycal = YearCalendar::from_calendar(
+            &cal,
+            2032,
+            &YearCalendarOptions {
+                church_year: true,
+                ..YearCalendarOptions::default()
+            },
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                ("fifth sunday".to_string(), NaiveDate::from_ymd(2032, 2, 29)),
+                ("leap".to_string(), NaiveDate::from_ymd(2032, 2, 29)),
+                ("leap 28".to_string(), NaiveDate::from_ymd(2032, 2, 29)),
+            ],
+            dates(&ycal)
+        );
+        assert_eq!(2, ycal.errors().len());
+        /* an holy day that cannot be placed is left out too */
+        let cal = calendar_of(&[
+            ("easter", calendar::HolydayClass::Principal, DateCal::Easter),
+            (
+                "weekday sunday",
+                calendar::HolydayClass::Sunday,
+                DateCal::Fixed { month: 3, day: 3 },
+            ),
+            (
+                "not a festival",
+                calendar::HolydayClass::NotAFestival,
+                DateCal::Fixed { month: 3, day: 4 },
+            ),
+        ]);
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &YearCalendarOptions::default(), false)
+            .unwrap();
+        assert_eq!(
+            vec![("easter".to_string(), NaiveDate::from_ymd(2021, 4, 4))],
+            dates(&ycal)
+        );
+        assert_eq!(1, ycal.holydays_by_date.len());
+        assert_eq!(2, ycal.errors().len());
+        for tag in ["weekday sunday", "not a festival"].iter() {
+            assert!(
+                ycal.errors()
+                    .iter()
+                    .any(|e| e.message().contains(&format!("of {} (", tag))),
+                "no error for {}",
+                tag
+            );
+        }
+    }
+    #[test]
+    fn test_bissextile() {
+        let matthias = DateCal::Bissextile { month: 2, day: 24 };
+        for (year, date) in [
+            (2020, NaiveDate::from_ymd(2020, 2, 25)),
+            (2021, NaiveDate::from_ymd(2021, 2, 24)),
+            (2024, NaiveDate::from_ymd(2024, 2, 25)),
+            (2100, NaiveDate::from_ymd(2100, 2, 24)),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                *date,
+                Year::new(*year)
+                    .date_cal_to_date(&matthias, calendar::LeapDayPolicy::default())
+                    .unwrap()
+            );
+        }
+        /* the other days of the year are not affected */
+        assert_eq!(
+            NaiveDate::from_ymd(2024, 2, 23),
+            Year::new(2024)
+                .date_cal_to_date(
+                    &DateCal::Bissextile { month: 2, day: 23 },
+                    calendar::LeapDayPolicy::default()
+                )
+                .unwrap()
+        );
+        /* in the BCP calendar; in 2024 St Matthias falls on the 2nd
+        Sunday in Lent, and is kept there as the 1662 Prayer Book does
+        not transfer holy days */
+        let cal =
+            calendar::Calendar::read(std::fs::File::open("data/final/bcp.data").unwrap()).unwrap();
+        for (year, date) in [
+            (2020, NaiveDate::from_ymd(2020, 2, 25)),
+            (2021, NaiveDate::from_ymd(2021, 2, 24)),
+            (2024, NaiveDate::from_ymd(2024, 2, 25)),
+        ]
+        .iter()
+        {
+            let ycal =
+                YearCalendar::from_calendar(&cal, *year, &YearCalendarOptions::default(), false)
+                    .unwrap();
+            let dates: Vec<NaiveDate> = ycal
+                .holydays_by_date
+                .values()
+                .flatten()
+                .filter(|ye| ye.holyday.tag() == "matthias")
+                .map(|ye| ye.date)
+                .collect();
+            assert_eq!(vec![*date], dates, "St Matthias in {}", year);
+        }
+    }
+    #[test]
+    fn test_calendar_range() {
+        let cal = calendar_from(
+            [
+                ("christmas", DateCal::Fixed { month: 12, day: 25 }),
+                ("easter", DateCal::Easter),
+            ]
+            .iter()
+            .cloned()
+            .map(|(tag, date_cal)| Holyday {
+                has_eve: true,
+                ..holyday(tag, calendar::HolydayClass::Principal, date_cal)
+            }),
+        );
+        let options = YearCalendarOptions {
+            eves: true,
+            ..YearCalendarOptions::default()
+        };
+        let uids = |years: RangeInclusive<i32>| -> Vec<String> {
+            let range = CalendarRange::from_calendar(&cal, years, &options, false).unwrap();
+            let (ical, ical_del) = range.to_ical("test");
+            let uids: Vec<String> = ical
+                .to_string()
+                .lines()
+                .filter(|l| l.starts_with("UID:"))
+                .map(|l| l.trim_start_matches("UID:").to_string())
+                .collect();
+            assert_eq!(
+                uids.len(),
+                ical_del.to_string().matches("CANCELLED").count()
+            );
+            uids
+        };
+        let uids_2021 = uids(2021..=2023);
+        assert_eq!(12, uids_2021.len());
+        assert!(uids_2021.contains(&"test-20220417-easter".to_string()));
+        assert!(uids_2021.contains(&"test-20231224-christmas-eve".to_string()));
+        let mut unique = uids_2021.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(uids_2021.len(), unique.len());
+        /* the same holy days have the same UIDs in an overlapping range */
+        let uids_2022 = uids(2022..=2024);
+        assert_eq!(
+            uids_2021[4..].to_vec(),
+            uids_2022[..8].to_vec(),
+            "2022 and 2023 should match"
+        );
+        /* and so does the iCal of a single year */
+        let (ical, _) = YearCalendar::from_calendar(&cal, 2022, &options, false)
+            .unwrap()
+            .to_ical("test");
+        let uids_single: Vec<String> = ical
+            .to_string()
+            .lines()
+            .filter(|l| l.starts_with("UID:"))
+            .map(|l| l.trim_start_matches("UID:").to_string())
+            .collect();
+        assert_eq!(uids_2021[4..8].to_vec(), uids_single);
+        /* Church years follow on from each other */
+        let church_years = CalendarRange::from_calendar(
+            &cal,
+            2021..=2022,
+            &YearCalendarOptions {
+                church_year: true,
+                ..YearCalendarOptions::default()
+            },
+            false,
+        )
+        .unwrap();
+        assert!(church_years.title().ends_with("Church years 2020–2022"));
+        let mut dates: Vec<NaiveDate> = church_years
+            .calendars()
+            .iter()
+            .flat_map(|ycal| ycal.holydays_by_date.keys().cloned())
+            .collect();
+        dates.sort();
+        assert_eq!(
+            vec![
+                NaiveDate::from_ymd(2020, 12, 25),
+                NaiveDate::from_ymd(2021, 4, 4),
+                NaiveDate::from_ymd(2021, 12, 25),
+                NaiveDate::from_ymd(2022, 4, 17),
+            ],
+            dates
+        );
+        assert!(CalendarRange::from_calendar(
+            &cal,
+            RangeInclusive::new(2022, 2021),
+            &options,
+            false
+        )
+        .is_err());
+        /* the UIDs are unique in a merged calendar with the same holy
+        day more than once on a date */
+        let all =
+            calendar::Calendar::read(std::fs::File::open("data/final/all.data").unwrap()).unwrap();
+        let (ical, _) = CalendarRange::from_calendar(&all, 2021..=2022, &options, false)
+            .unwrap()
+            .to_ical("x");
+        let uids: Vec<String> = ical
+            .to_string()
+            .lines()
+            .filter(|l| l.starts_with("UID:"))
+            .map(|l| l.trim_start_matches("UID:").to_string())
+            .collect();
+        assert!(uids.contains(&"x-20210302-chad".to_string()));
+        assert!(uids.contains(&"x-20210302-chad-2".to_string()));
+        let mut unique = uids.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(uids.len(), unique.len());
+    }
+    #[test]
+    fn test_on() {
+        let cal = calendar_of(&[
+            ("easter", calendar::HolydayClass::Principal, DateCal::Easter),
+            (
+                "lent festival",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 3, day: 21 },
+            ),
+            (
+                "lent commemoration",
+                calendar::HolydayClass::Commemoration,
+                DateCal::Fixed { month: 3, day: 22 },
+            ),
+        ]);
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &YearCalendarOptions::default(), false)
+            .unwrap();
+        let date = |m, d| NaiveDate::from_ymd(2021, m, d);
+        let on = ycal.on(date(4, 4));
+        assert_eq!(1, on.len());
+        assert_eq!("easter", on[0].title());
+        assert!(!on[0].is_transferred());
+        // the festival falls on the 5th Sunday of Lent so is transferred
+        assert!(ycal.on(date(3, 21)).is_empty());
+        let on = ycal.on(date(3, 22));
+        assert_eq!(
+            vec!["lent festival", "lent commemoration"],
+            on.iter().map(|ye| ye.title()).collect::<Vec<_>>()
+        );
+        assert!(on[0].is_transferred());
+        assert_eq!(date(3, 21), on[0].nominal_date());
+        assert_eq!(date(3, 22), on[0].date());
+        assert!(!on[1].is_transferred());
+        assert_eq!(
+            "lent festival (Festival, White) transferred from Sunday 21 March (on a Sunday)",
+            ycal.describe(on[0])
+        );
+        assert_eq!(3, ycal.between(date(3, 1), date(4, 30)).len());
+        assert_eq!(
+            vec!["lent commemoration", "easter"],
+            ycal.next_n(date(3, 22), 3)
+                .iter()
+                .skip(1)
+                .map(|ye| ye.title())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(1, ycal.next_n(date(1, 1), 1).len());
+        assert!(ycal.next_n(date(4, 5), 3).is_empty());
+    }
+    #[test]
+    fn test_find_by_tag() {
+        let cal = calendar_from(
+            [
+                ("easter", calendar::HolydayClass::Principal, DateCal::Easter),
+                (
+                    "advent festival",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 12, day: 5 },
+                ),
+                (
+                    "lesser festival",
+                    calendar::HolydayClass::LesserFestival,
+                    DateCal::Fixed { month: 12, day: 5 },
+                ),
+            ]
+            .iter()
+            .cloned()
+            .map(|(tag, class, date_cal)| Holyday {
+                has_eve: true,
+                ..holyday(tag, class, date_cal)
+            }),
+        );
+        let options = YearCalendarOptions {
+            eves: true,
+            ..YearCalendarOptions::default()
+        };
+        let find = |year| -> Vec<(NaiveDate, Option<TransferReason>)> {
+            let ycal = YearCalendar::from_calendar(&cal, year, &options, false).unwrap();
+            ycal.find_by_tag("advent festival")
+                .iter()
+                .map(|ye| (ye.date(), ye.transfer_reason()))
+                .collect()
+        };
+        // 5 December 2021 is the 2nd Sunday of Advent, and the eve is not
+        // found
+        assert_eq!(
+            vec![(
+                NaiveDate::from_ymd(2021, 12, 6),
+                Some(TransferReason::Sunday)
+            )],
+            find(2021)
+        );
+        assert_eq!(vec![(NaiveDate::from_ymd(2022, 12, 5), None)], find(2022));
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &options, false).unwrap();
+        assert!(ycal.find_by_tag("lesser festival").is_empty());
+        let dropped = ycal.dropped_by_tag("lesser festival");
+        assert_eq!(1, dropped.len());
+        assert_eq!(NaiveDate::from_ymd(2021, 12, 5), dropped[0].0.date());
+        assert_eq!(DropReason::Sunday, dropped[0].1);
+        assert!(ycal.dropped_by_tag("advent festival").is_empty());
+        assert_eq!(1, ycal.find_by_tag("easter").len());
+    }
+    #[test]
+    fn test_transfer_rules() {
+        let rules = |r: &str| -> TransferType { ron::de::from_str(r).unwrap() };
+        let cal = calendar_from(
+            [
+                (
+                    "christmas",
+                    calendar::HolydayClass::Principal,
+                    DateCal::Fixed { month: 12, day: 25 },
+                    TransferType::Normal,
+                ),
+                // 2021-12-05 is the 2nd Sunday of Advent
+                (
+                    "advent drop",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 12, day: 5 },
+                    rules("Rules([(when: [OnSunday, In(Advent)], action: Drop)])"),
+                ),
+                // 2021-11-06 is a Saturday
+                (
+                    "next weekday",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 11, day: 6 },
+                    rules(
+                        "Rules([(
+                            when: [In(Between(Fixed(month: 11, day: 1), Fixed(month: 11, day: 7)))],
+                            action: NextWeekday,
+                        )])",
+                    ),
+                ),
+                (
+                    "forever",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 6, day: 1 },
+                    rules("Rules([(action: NextDay)])"),
+                ),
+                (
+                    "clash",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 12, day: 25 },
+                    rules(
+                        "Rules([
+                            (when: [ClashesWith(Principal)], action: MoveTo(Fixed(month: 12, day: 29))),
+                            (when: [ClashesWith(Festival)], action: Drop),
+                        ])",
+                    ),
+                ),
+                // Easter 2021 is 4 April
+                (
+                    "in easter",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 4, day: 1 },
+                    rules(
+                        "Rules([
+                            (when: [In(LentOrEastertide), OnSunday], action: Drop),
+                            (
+                                when: [DateIn(Easter, Between(Fixed(month: 4, day: 1), Fixed(month: 4, day: 7)))],
+                                action: Keep,
+                            ),
+                            (action: Drop),
+                        ])",
+                    ),
+                ),
+            ]
+            .iter()
+            .cloned()
+            .map(|(tag, class, date_cal, transfer)| Holyday { transfer, ..holyday(tag, class, date_cal) }),
+        );
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &YearCalendarOptions::default(), false)
+            .unwrap();
+        let found = |tag| -> Vec<(NaiveDate, Option<TransferReason>)> {
+            ycal.find_by_tag(tag)
+                .iter()
+                .map(|ye| (ye.date(), ye.transfer_reason()))
+                .collect()
+        };
+        assert!(found("advent drop").is_empty());
+        assert_eq!(DropReason::Sunday, ycal.dropped_by_tag("advent drop")[0].1);
+        assert_eq!(
+            vec![(NaiveDate::from_ymd(2021, 11, 8), Some(TransferReason::Rule))],
+            found("next weekday")
+        );
+        assert_eq!(
+            vec![(
+                NaiveDate::from_ymd(2021, 12, 29),
+                Some(TransferReason::Clash)
+            )],
+            found("clash")
+        );
+        assert_eq!(
+            vec![(NaiveDate::from_ymd(2021, 4, 1), None)],
+            found("in easter")
+        );
+        assert!(found("forever").is_empty());
+        assert_eq!(1, ycal.errors().len());
+        assert!(ycal.errors()[0].message().contains("forever"));
+    }
+    #[test]
+    fn test_cascading_transfers() {
+        let cal = calendar_from(
+            [
+                (
+                    "easter",
+                    calendar::HolydayClass::Principal,
+                    DateCal::Easter,
+                    TransferType::Normal,
+                ),
+                (
+                    "annunciation",
+                    calendar::HolydayClass::Principal,
+                    DateCal::Fixed { month: 3, day: 25 },
+                    TransferType::Annunciation,
+                ),
+                (
+                    "joseph",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 3, day: 19 },
+                    TransferType::Joseph,
+                ),
+                (
+                    "george",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 4, day: 23 },
+                    TransferType::George,
+                ),
+                (
+                    "mark",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 4, day: 25 },
+                    TransferType::Mark,
+                ),
+                (
+                    "lent principal",
+                    calendar::HolydayClass::Principal,
+                    DateCal::Fixed { month: 3, day: 10 },
+                    TransferType::Normal,
+                ),
+                (
+                    "lent festival",
+                    calendar::HolydayClass::Festival,
+                    DateCal::Fixed { month: 3, day: 9 },
+                    TransferType::Normal,
+                ),
+            ]
+            .iter()
+            .cloned()
+            .map(|(tag, class, date_cal, transfer)| Holyday {
+                transfer,
+                ..holyday(tag, class, date_cal)
+            }),
+        );
+        let dates = |year| -> Vec<(String, NaiveDate)> {
+            let ycal =
+                YearCalendar::from_calendar(&cal, year, &YearCalendarOptions::default(), false)
+                    .unwrap();
+            for (date, yee) in &ycal.holydays_by_date {
+                assert!(
+                    yee.iter().all(|ye| ye.date == *date),
+                    "stored under {}",
+                    date
+                );
+            }
+            let mut dates: Vec<(String, NaiveDate)> = ycal
+                .between(
+                    NaiveDate::from_ymd(year, 1, 1),
+                    NaiveDate::from_ymd(year, 12, 31),
+                )
+                .iter()
+                .map(|ye| (ye.title(), ye.date()))
+                .collect();
+            dates.sort();
+            dates
+        };
+        let date = |y, m, d| NaiveDate::from_ymd(y, m, d);
+        // Easter 2008 is 23 March, so Joseph is in Holy Week and the
+        // Annunciation in Easter Week; both go after the 2nd Sunday of
+        // Easter, the Annunciation first. The festival on the 5th Sunday of Lent moves
+        // to the Monday, where there is a Principal Feast, so it moves
+        // again.
+        assert_eq!(
+            vec![
+                ("annunciation".to_string(), date(2008, 3, 31)),
+                ("easter".to_string(), date(2008, 3, 23)),
+                ("george".to_string(), date(2008, 4, 23)),
+                ("joseph".to_string(), date(2008, 4, 1)),
+                ("lent festival".to_string(), date(2008, 3, 11)),
+                ("lent principal".to_string(), date(2008, 3, 10)),
+                ("mark".to_string(), date(2008, 4, 25)),
+            ],
+            dates(2008)
+        );
+        // Easter 2011 is 24 April, so George and Mark are both in Holy
+        // Week or Easter Week; both go after the 2nd Sunday of Easter,
+        // George first
+        assert_eq!(
+            vec![
+                ("annunciation".to_string(), date(2011, 3, 25)),
+                ("easter".to_string(), date(2011, 4, 24)),
+                ("george".to_string(), date(2011, 5, 2)),
+                ("joseph".to_string(), date(2011, 3, 19)),
+                ("lent festival".to_string(), date(2011, 3, 9)),
+                ("lent principal".to_string(), date(2011, 3, 10)),
+                ("mark".to_string(), date(2011, 5, 3)),
+            ],
+            dates(2011)
+        );
+        // Easter 2035 is 25 March, so the Annunciation is on Easter Day
+        // and Joseph is on the Monday of Holy Week
+        let dates_2035 = dates(2035);
+        assert!(dates_2035.contains(&("annunciation".to_string(), date(2035, 4, 2))));
+        assert!(dates_2035.contains(&("joseph".to_string(), date(2035, 4, 3))));
+    }
+    #[test]
+    fn test_placement_order() {
+        let placed = |cal: &calendar::Calendar, year, sundays| -> Vec<(String, NaiveDate)> {
+            let options = YearCalendarOptions {
+                sundays,
+                eves: true,
+                ..YearCalendarOptions::default()
+            };
+            let ycal = YearCalendar::from_calendar(cal, year, &options, false).unwrap();
+            ycal.between(
+                NaiveDate::from_ymd(year, 1, 1),
+                NaiveDate::from_ymd(year, 12, 31),
+            )
+            .iter()
+            .map(|ye| (ye.title(), ye.date()))
+            .chain(
+                ycal.dropped
+                    .iter()
+                    .map(|(ye, _)| (format!("dropped {}", ye.title()), ye.date())),
+            )
+            .collect()
+        };
+        let in_order = |holydays: &[Holyday]| -> calendar::Calendar {
+            let mut cal = calendar::Calendar::new();
+            cal.province = calendar::Province::ChurchOfEngland;
+            cal.days_of_prayer = true;
+            for holyday in holydays {
+                cal.add(holyday);
+            }
+            cal
+        };
+        let cofe =
+            calendar::Calendar::read(std::fs::File::open("data/final/cofe.data").unwrap()).unwrap();
+        let mut holydays: Vec<Holyday> = cofe
+            .get_holydays()
+            .iter()
+            .map(|hr| Holyday::from(hr.clone()))
+            .collect();
+        /* two Sundays on the same date, so one must give way to the other */
+        for tag in ["sunday b", "sunday a"].iter() {
+            holydays.push(Holyday {
+                title: tag.to_string(),
+                tag: tag.to_string(),
+                class: calendar::HolydayClass::Sunday,
+                date_cal: DateCal::Next {
+                    date: Box::new(DateCal::Fixed { month: 7, day: 1 }),
+                    day_of_week: chrono::Weekday::Sun.into(),
+                },
+                ..Holyday::default()
+            });
+        }
+        let forward = in_order(&holydays);
+        holydays.reverse();
+        let backward = in_order(&holydays);
+        let third = holydays.len() / 3;
+        holydays.rotate_left(third);
+        let rotated = in_order(&holydays);
+        for year in [2008, 2011, 2021, 2035].iter() {
+            let expected = placed(&forward, *year, true);
+            assert_eq!(
+                expected,
+                placed(&backward, *year, true),
+                "reversed {}",
+                year
+            );
+            assert_eq!(expected, placed(&rotated, *year, true), "rotated {}", year);
+        }
+        /* without the named Sundays, the first of the two by tag is kept */
+        let sundays: Vec<(String, NaiveDate)> = placed(&backward, 2021, false)
+            .into_iter()
+            .filter(|(title, _)| title.starts_with("sunday") || title.starts_with("dropped sunday"))
+            .collect();
+        assert_eq!(
+            vec![
+                ("sunday a".to_string(), NaiveDate::from_ymd(2021, 7, 4)),
+                (
+                    "dropped sunday b".to_string(),
+                    NaiveDate::from_ymd(2021, 7, 4)
+                ),
+            ],
+            sundays
+        );
+        /* the holy days with the built-in rules are placed in date order
+        with the others of the same precedence, whatever the order of the
+        calendar */
+        let holydays: Vec<Holyday> = [
+            (
+                "joseph",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 3, day: 19 },
+                TransferType::Joseph,
+            ),
+            (
+                "annunciation",
+                calendar::HolydayClass::Principal,
+                DateCal::Fixed { month: 3, day: 25 },
+                TransferType::Annunciation,
+            ),
+            (
+                "easter",
+                calendar::HolydayClass::Principal,
+                DateCal::Easter,
+                TransferType::Normal,
+            ),
+            (
+                "george",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 4, day: 23 },
+                TransferType::George,
+            ),
+            (
+                "mark",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 4, day: 25 },
+                TransferType::Mark,
+            ),
+            (
+                "philip and james",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 5, day: 1 },
+                TransferType::Normal,
+            ),
+        ]
+        .iter()
+        .cloned()
+        .map(|(tag, class, date_cal, transfer)| Holyday {
+            transfer,
+            ..holyday(tag, class, date_cal)
+        })
+        .collect();
+        let date_of = |placed: &[(String, NaiveDate)], tag: &str| -> Option<NaiveDate> {
+            placed.iter().find(|(t, _)| t == tag).map(|(_, d)| *d)
+        };
+        let forward = in_order(&holydays);
+        let backward = in_order(&holydays.iter().rev().cloned().collect::<Vec<_>>());
+        for cal in [forward, backward].iter() {
+            /* Easter 2008 is 23 March, so Joseph (in Holy Week) goes after
+            the Annunciation (in Easter Week) */
+            let placed_2008 = placed(cal, 2008, false);
+            assert_eq!(
+                Some(NaiveDate::from_ymd(2008, 3, 31)),
+                date_of(&placed_2008, "annunciation")
+            );
+            assert_eq!(
+                Some(NaiveDate::from_ymd(2008, 4, 1)),
+                date_of(&placed_2008, "joseph")
+            );
+            /* Easter 2011 is 24 April, so George and Mark go after the 2nd
+            Sunday of Easter (1 May); George is placed before Philip and
+            James, moved off that Sunday, so it is not moved again */
+            let placed_2011 = placed(cal, 2011, false);
+            assert_eq!(
+                vec![
+                    Some(NaiveDate::from_ymd(2011, 5, 2)),
+                    Some(NaiveDate::from_ymd(2011, 5, 3)),
+                    Some(NaiveDate::from_ymd(2011, 5, 2)),
+                ],
+                ["george", "mark", "philip and james"]
+                    .iter()
+                    .map(|tag| date_of(&placed_2011, tag))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+    #[test]
+    fn test_readings() {
+        let cal = calendar_from(
+            [
+                ("christmas", DateCal::Fixed { month: 12, day: 25 }),
+                ("easter", DateCal::Easter),
+                (
+                    "pentecost",
+                    DateCal::After {
+                        date: Box::new(DateCal::Easter),
+                        rel: 49,
+                    },
+                ),
+            ]
+            .iter()
+            .cloned()
+            .map(|(tag, date_cal)| Holyday {
+                has_eve: true,
+                ..holyday(tag, calendar::HolydayClass::Principal, date_cal)
+            }),
+        );
+        let readings = readings::Readings::read(
+            r#"(
+                province: ChurchOfEngland,
+                readings: [
+                    (tag: "christmas", readings: ["Luke 2.1-14"]),
+                    (tag: "easter", year: Some(B), readings: ["Mark 16.1-8"]),
+                    (tag: "pentecost", year: Some(A), readings: ["John 20.19-23"]),
+                ],
+            )"#
+            .as_bytes(),
+        )
+        .unwrap();
+        let options = YearCalendarOptions {
+            eves: true,
+            ..YearCalendarOptions::default()
+        };
+        let mut ycal = YearCalendar::from_calendar(&cal, 2021, &options, false).unwrap();
+        assert_eq!(vec!["pentecost".to_string()], ycal.add_readings(&readings));
+        let readings_on = |date: NaiveDate| -> Vec<Option<Vec<String>>> {
+            ycal.holydays_by_date[&date]
+                .iter()
+                .map(|ye| ye.readings().cloned())
+                .collect()
+        };
+        assert_eq!(
+            vec![Some(vec!["Mark 16.1-8".to_string()])],
+            readings_on(NaiveDate::from_ymd(2021, 4, 4))
+        );
+        /* Christmas 2021 is in Year C, and its eve has no readings */
+        assert_eq!(
+            vec![Some(vec!["Luke 2.1-14".to_string()])],
+            readings_on(NaiveDate::from_ymd(2021, 12, 25))
+        );
+        assert_eq!(vec![None], readings_on(NaiveDate::from_ymd(2021, 12, 24)));
+        /* the cofe lectionary has every Principal Feast of cofe.data in
+        each year of the Sunday cycle */
+        let cofe =
+            calendar::Calendar::read(std::fs::File::open("data/final/cofe.data").unwrap()).unwrap();
+        let readings =
+            readings::Readings::read(std::fs::File::open("data/readings/cofe.readings").unwrap())
+                .unwrap();
+        for year in 2020..=2022 {
+            let mut ycal = YearCalendar::from_calendar(&cofe, year, &options, false).unwrap();
+            assert_eq!(
+                Vec::<String>::new(),
+                ycal.add_readings(&readings),
+                "{}",
+                year
+            );
+        }
+    }
+    #[test]
+    fn test_colours() {
+        use crate::colours::ColourRules;
+        use calendar::SeasonColour;
+        let year = Year::new(2021);
+        let holyday = |tag: &str, class, martyr: bool| {
+            let mut main = HashSet::new();
+            if martyr {
+                main.insert(calendar::MainAttribute::Martyr);
+            }
+            calendar::HolydayRef::new(Holyday {
+                tag: tag.to_string(),
+                class,
+                main,
+                ..Holyday::default()
+            })
+        };
+        let annunciation = holyday("annunciation", calendar::HolydayClass::Principal, false);
+        let george = holyday("george", calendar::HolydayClass::Festival, true);
+        let joseph = holyday("joseph", calendar::HolydayClass::Festival, false);
+        let mark = holyday("mark", calendar::HolydayClass::Festival, false);
+        let lent = NaiveDate::from_ymd(2021, 3, 25);
+        let cw = ColourRules::common_worship().unwrap();
+        assert_eq!(SeasonColour::Purple, cw.season_colour(&year, lent));
+        assert_eq!(SeasonColour::Purple, year.season_colour(lent).unwrap());
+        assert_eq!(
+            SeasonColour::White,
+            cw.holyday_colour(&annunciation, &year, lent)
+        );
+        assert_eq!(SeasonColour::Red, cw.holyday_colour(&george, &year, lent));
+        assert_eq!(SeasonColour::White, cw.holyday_colour(&joseph, &year, lent));
+        assert_eq!(SeasonColour::Red, cw.holyday_colour(&mark, &year, lent));
+        /* Trinity Sunday, and a church year date in the previous calendar year */
+        assert_eq!(
+            SeasonColour::White,
+            cw.season_colour(&year, NaiveDate::from_ymd(2021, 5, 30))
+        );
+        assert_eq!(
+            SeasonColour::Purple,
+            cw.season_colour(&year, NaiveDate::from_ymd(2020, 12, 6))
+        );
+        for province in ["cofe", "ecusa", "aca", "bcp"].iter() {
+            let rules = ColourRules::read(
+                std::fs::File::open(format!("data/colours/{}.colours", province)).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(SeasonColour::Purple, rules.season_colour(&year, lent));
+        }
+        /* the BCP keeps purple from Septuagesima, ECUSA keeps the
+        Baptism of Our Lord in white */
+        let bcp =
+            ColourRules::read(std::fs::File::open("data/colours/bcp.colours").unwrap()).unwrap();
+        let septuagesima = NaiveDate::from_ymd(2021, 1, 31);
+        assert_eq!(
+            SeasonColour::Green,
+            cw.season_colour(&year, NaiveDate::from_ymd(2021, 2, 7))
+        );
+        assert_eq!(SeasonColour::Purple, bcp.season_colour(&year, septuagesima));
+        let ecusa =
+            ColourRules::read(std::fs::File::open("data/colours/ecusa.colours").unwrap()).unwrap();
+        let baptism = NaiveDate::from_ymd(2021, 1, 10);
+        assert_eq!(SeasonColour::White, ecusa.season_colour(&year, baptism));
+        assert_eq!(
+            SeasonColour::Green,
+            ecusa.season_colour(&year, baptism + Duration::days(1))
+        );
+        /* optional colours */
+        let mut ecusa = ecusa;
+        let advent = NaiveDate::from_ymd(2021, 12, 5);
+        assert_eq!(SeasonColour::Purple, ecusa.season_colour(&year, advent));
+        ecusa.enable(crate::colours::ColourOption::Blue);
+        ecusa.enable(crate::colours::ColourOption::LentenArray);
+        assert_eq!(SeasonColour::Blue, ecusa.season_colour(&year, advent));
+        assert_eq!(
+            SeasonColour::UnbleachedLinen,
+            ecusa.season_colour(&year, lent)
+        );
+        assert_eq!("linen", SeasonColour::UnbleachedLinen.colour_a());
+    }
+    #[test]
+    fn test_practices() {
+        let cal = calendar_of(&[
+            (
+                "epiphany",
+                calendar::HolydayClass::Principal,
+                DateCal::Fixed { month: 1, day: 6 },
+            ),
+            (
+                "presentation",
+                calendar::HolydayClass::Principal,
+                DateCal::Fixed { month: 2, day: 2 },
+            ),
+            (
+                "all saints",
+                calendar::HolydayClass::Principal,
+                DateCal::Fixed { month: 11, day: 1 },
+            ),
+            (
+                "matthias",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 5, day: 14 },
+            ),
+            (
+                "thomas",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 7, day: 3 },
+            ),
+            (
+                "corpus christi",
+                calendar::HolydayClass::CorpusChristi,
+                DateCal::After {
+                    date: Box::new(DateCal::Easter),
+                    rel: 60,
+                },
+            ),
+        ]);
+        let kept =
+            |options: &YearCalendarOptions| -> Vec<(String, NaiveDate, calendar::HolydayClass)> {
+                let ycal = YearCalendar::from_calendar(&cal, 2021, options, false).unwrap();
+                let mut kept: Vec<_> = ycal
+                    .holydays_by_date
+                    .values()
+                    .flatten()
+                    .map(|ye| (ye.holyday.tag(), ye.date, ye.holyday.class()))
+                    .collect();
+                kept.sort();
+                kept
+            };
+        let date = |m, d| NaiveDate::from_ymd(2021, m, d);
+        assert_eq!(
+            vec![
+                (
+                    "all saints".to_string(),
+                    date(11, 1),
+                    calendar::HolydayClass::Principal
+                ),
+                (
+                    "corpus christi".to_string(),
+                    date(6, 3),
+                    calendar::HolydayClass::CorpusChristi
+                ),
+                (
+                    "epiphany".to_string(),
+                    date(1, 6),
+                    calendar::HolydayClass::Principal
+                ),
+                (
+                    "matthias".to_string(),
+                    date(5, 14),
+                    calendar::HolydayClass::Festival
+                ),
+                (
+                    "presentation".to_string(),
+                    date(2, 2),
+                    calendar::HolydayClass::Principal
+                ),
+                (
+                    "thomas".to_string(),
+                    date(7, 3),
+                    calendar::HolydayClass::Festival
+                ),
+            ],
+            kept(&YearCalendarOptions::default())
+        );
+        let practices = CommonWorshipOptions::read(
+            &b"(epiphany_on_sunday: true, presentation_on_sunday: true, all_saints_on_sunday: true)"[..],
+        )
+        .unwrap();
+        let mut options = YearCalendarOptions {
+            sundays: true,
+            practices,
+            ..YearCalendarOptions::default()
+        };
+        for practice in [
+            "matthias-in-february",
+            "thomas-in-december",
+            "corpus-christi-as-festival",
+        ]
+        .iter()
+        {
+            options.practices.enable(practice.parse().unwrap());
+        }
+        let kept = kept(&options);
+        for expected in [
+            (
+                "all saints",
+                date(10, 31),
+                calendar::HolydayClass::Principal,
+            ),
+            (
+                "corpus christi",
+                date(6, 3),
+                calendar::HolydayClass::Festival,
+            ),
+            ("epiphany", date(1, 3), calendar::HolydayClass::Principal),
+            ("matthias", date(2, 24), calendar::HolydayClass::Festival),
+            (
+                "presentation",
+                date(1, 31),
+                calendar::HolydayClass::Principal,
+            ),
+            ("thomas", date(12, 21), calendar::HolydayClass::Festival),
+        ]
+        .iter()
+        {
+            assert!(
+                kept.contains(&(expected.0.to_string(), expected.1, expected.2)),
+                "{:?}",
+                expected
+            );
+        }
+        /* the holy days replace the Sundays */
+        for sunday in [date(1, 3), date(1, 31), date(10, 31)].iter() {
+            assert_eq!(1, kept.iter().filter(|(_, d, _)| d == sunday).count());
+        }
+        /* an holy day that is not on its usual date is not moved */
+        let matthias = calendar::HolydayRef::new(Holyday {
+            tag: "matthias".to_string(),
+            date_cal: DateCal::Bissextile { month: 2, day: 24 },
+            ..Holyday::default()
+        });
+        assert_eq!(matthias, options.practices.apply(&matthias));
+        assert!("matthias-in-march".parse::<Practice>().is_err());
+    }
+    #[test]
+    fn test_parish() {
+        use crate::parish::{Parish, ParishFestival, ParishKeeping};
+        let festival = |month, day, keep| {
+            Some(ParishFestival {
+                title: "test".to_string(),
+                date_cal: DateCal::Fixed { month, day },
+                keep,
+                martyr: false,
+                replaces: None,
+            })
+        };
+        let kept =
+            |year, patronal, dedication| -> Vec<(String, NaiveDate, calendar::HolydayClass)> {
+                let mut cal =
+                    calendar_of(&[("easter", calendar::HolydayClass::Principal, DateCal::Easter)]);
+                Parish {
+                    info: calendar::FileInfo::default(),
+                    name: "test".to_string(),
+                    patronal,
+                    dedication,
+                }
+                .apply(&mut cal);
+                let ycal =
+                    YearCalendar::from_calendar(&cal, year, &YearCalendarOptions::default(), false)
+                        .unwrap();
+                let mut kept: Vec<_> = ycal
+                    .holydays_by_date
+                    .values()
+                    .flatten()
+                    .filter(|ye| ye.holyday.tag().ends_with(" festival"))
+                    .map(|ye| (ye.holyday.tag(), ye.date, ye.holyday.class()))
+                    .collect();
+                kept.sort();
+                kept
+            };
+        let principal = calendar::HolydayClass::Principal;
+        let patronal = |date| ("patronal festival".to_string(), date, principal);
+        let dedication = |date| ("dedication festival".to_string(), date, principal);
+        /* on the nearest Sunday */
+        assert_eq!(
+            vec![patronal(NaiveDate::from_ymd(2021, 9, 26))],
+            kept(2021, festival(9, 29, ParishKeeping::Sunday), None)
+        );
+        /* not on a Principal Feast, whichever is placed first */
+        assert_eq!(
+            vec![
+                dedication(NaiveDate::from_ymd(2022, 10, 2)),
+                patronal(NaiveDate::from_ymd(2022, 9, 29)),
+            ],
+            kept(
+                2022,
+                festival(9, 29, ParishKeeping::Sunday),
+                festival(10, 2, ParishKeeping::Principal)
+            )
+        );
+        assert_eq!(
+            vec![dedication(NaiveDate::from_ymd(2024, 4, 1))],
+            kept(2024, None, festival(4, 1, ParishKeeping::Sunday))
+        );
+        /* not on the 5th Sunday of Lent or Advent Sunday */
+        assert_eq!(
+            vec![patronal(NaiveDate::from_ymd(2023, 3, 24))],
+            kept(2023, festival(3, 24, ParishKeeping::Sunday), None)
+        );
+        assert_eq!(
+            vec![patronal(NaiveDate::from_ymd(2024, 11, 30))],
+            kept(2024, festival(11, 30, ParishKeeping::Sunday), None)
+        );
+        /* as a Festival on its date */
+        assert_eq!(
+            vec![(
+                "patronal festival".to_string(),
+                NaiveDate::from_ymd(2021, 9, 29),
+                calendar::HolydayClass::Festival
+            )],
+            kept(2021, festival(9, 29, ParishKeeping::Festival), None)
+        );
+        /* the example parish replaces St Michael and All Angels */
+        let mut cal =
+            calendar::Calendar::read(std::fs::File::open("data/final/cofe.data").unwrap()).unwrap();
+        Parish::read(std::fs::File::open("data/parishes/example.parish").unwrap())
+            .unwrap()
+            .apply(&mut cal);
+        assert!(cal.get_by_tag("michael all angels").is_err());
+        let ycal = YearCalendar::from_calendar(&cal, 2022, &YearCalendarOptions::default(), false)
+            .unwrap();
+        assert_eq!(
+            vec![NaiveDate::from_ymd(2022, 9, 29)],
+            ycal.find_by_tag("patronal festival")
+                .iter()
+                .map(|ye| ye.date())
+                .collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn test_layered_calendar() {
+        let mut cal = calendar::Calendar::read_layered("data/layers/example.manifest").unwrap();
+        assert_eq!(calendar::Province::ChurchOfEngland, cal.province);
+        /* the parish overlay is applied after the diocese overlay */
+        let richard = cal.get_by_tag("richard").unwrap();
+        assert_eq!(calendar::HolydayClass::Festival, richard.class());
+        for (field, layer) in [
+            ("class", "St Richard, Anytown"),
+            ("has_eve", "St Richard, Anytown"),
+            ("description", "St Richard, Anytown"),
+            ("date_cal", "Church of England"),
+            ("title", "Church of England"),
+        ]
+        .iter()
+        {
+            assert_eq!(Some(layer.to_string()), richard.set_by(field), "{}", field);
+        }
+        assert_eq!(
+            "Richard, Bishop of Chichester, patron of the parish",
+            richard.description()
+        );
+        let cuthman = cal.get_by_tag("cuthman").unwrap();
+        assert_eq!(
+            Some("Diocese of Chichester".to_string()),
+            cuthman.set_by("date_cal")
+        );
+        let easter = cal.get_by_tag("easter").unwrap();
+        assert_eq!(
+            Some("Church of England".to_string()),
+            easter.set_by("class")
+        );
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &YearCalendarOptions::default(), false)
+            .unwrap();
+        assert_eq!(
+            vec![NaiveDate::from_ymd(2021, 2, 8)],
+            ycal.find_by_tag("cuthman")
+                .iter()
+                .map(|ye| ye.date())
+                .collect::<Vec<_>>()
+        );
+        /* a file that cannot be opened is named in the error */
+        let err = calendar::Calendar::read_layered("data/layers/missing.manifest").unwrap_err();
+        assert!(
+            err.message()
+                .starts_with("cannot open data/layers/missing.manifest"),
+            "{}",
+            err.message()
+        );
+        /* a calendar that is not layered has no provenance, and it is
+        not written to the calendar file */
+        let mut cal =
+            calendar::Calendar::read(std::fs::File::open("data/final/cofe.data").unwrap()).unwrap();
+        assert_eq!(None, cal.get_by_tag("richard").unwrap().set_by("class"));
+        let mut written = vec![];
+        cal.write(&mut written).unwrap();
+        assert!(!String::from_utf8(written).unwrap().contains("provenance"));
+    }
+    #[test]
+    fn test_decisions() {
+        let cal =
+            calendar::Calendar::read(std::fs::File::open("data/final/cofe.data").unwrap()).unwrap();
+        let options = YearCalendarOptions {
+            sundays: true,
+            ..YearCalendarOptions::default()
+        };
+        /* 23 April 2011 is Holy Saturday, so George goes after the 2nd
+        Sunday of Easter */
+        let ycal = YearCalendar::from_calendar(&cal, 2011, &options, false).unwrap();
+        let why = ycal.why("george");
+        assert_eq!(
+            vec![
+                (
+                    NaiveDate::from_ymd(2011, 4, 23),
+                    Outcome::Moved(NaiveDate::from_ymd(2011, 5, 2)),
+                    PlacementRule::TransferRule(1),
+                ),
+                (
+                    NaiveDate::from_ymd(2011, 5, 2),
+                    Outcome::Kept,
+                    PlacementRule::NoTransferRule,
+                ),
+            ],
+            why.iter()
+                .map(|d| (d.date(), d.outcome(), d.rule()))
+                .collect::<Vec<_>>()
+        );
+        for d in &why {
+            assert_eq!(NaiveDate::from_ymd(2011, 4, 23), d.nominal_date());
+            assert_eq!(Some(NaiveDate::from_ymd(2011, 5, 2)), d.final_date());
+            assert_eq!(None, d.clash());
+        }
+        assert_eq!(
+            "George (Saturday 23 April): moved to Monday 2 May because transfer rule 1 of the holy day",
+            why[0].to_string()
+        );
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &options, false).unwrap();
+        /* a lesser festival on a Sunday gives way to the Sunday */
+        let why = ycal.why("valentine");
+        assert_eq!(1, why.len());
+        assert_eq!(Outcome::Dropped(DropReason::Clash), why[0].outcome());
+        assert_eq!(PlacementRule::GivesWay, why[0].rule());
+        assert_eq!(
+            Some("sunday next before lent".to_string()),
+            why[0].clash().map(|c| c.tag())
+        );
+        assert_eq!(None, why[0].final_date());
+        /* the Blessed Virgin Mary on 15 August 2021 takes the place of
+        the Sunday */
+        let why = ycal.why("11th sunday after trinity");
+        assert_eq!(1, why.len());
+        assert_eq!(PlacementRule::FestivalReplacesSunday, why[0].rule());
+        assert_eq!(Some("mary".to_string()), why[0].clash().map(|c| c.tag()));
+        assert_eq!(
+            DropReason::Clash,
+            ycal.dropped_by_tag("11th sunday after trinity")[0].1
+        );
+        /* a Sunday that is replaced by a festival placed after it has
+        only the decision that it was dropped */
+        let mut ycal = YearCalendar::from_calendar(
+            &calendar_of(&[(
+                "sunday",
+                calendar::HolydayClass::Sunday,
+                DateCal::Fixed { month: 6, day: 20 },
+            )]),
+            2021,
+            &YearCalendarOptions::default(),
+            false,
+        )
+        .unwrap();
+        let year = ycal.year.clone();
+        let mut festival = YearHolyday::from_holyday(
+            &calendar::HolydayRef::new(holyday(
+                "festival",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 6, day: 20 },
+            )),
+            &year,
+        )
+        .unwrap();
+        ycal.add(&mut festival, &year, false).unwrap();
+        let why = ycal.why("sunday");
+        assert_eq!(1, why.len());
+        assert_eq!(Outcome::Dropped(DropReason::Clash), why[0].outcome());
+        assert_eq!(PlacementRule::FestivalReplacesSunday, why[0].rule());
+        assert_eq!(
+            Some("festival".to_string()),
+            why[0].clash().map(|c| c.tag())
+        );
+        assert_eq!(None, why[0].final_date());
+        assert!(ycal.find_by_tag("sunday").is_empty());
+        let cal =
+            calendar::Calendar::read(std::fs::File::open("data/final/cofe.data").unwrap()).unwrap();
+        let ycal = YearCalendar::from_calendar(&cal, 2021, &options, false).unwrap();
+        /* every holy day that is kept has a decision on the date it is
+        kept */
+        for ye in ycal.between(
+            NaiveDate::from_ymd(2021, 1, 1),
+            NaiveDate::from_ymd(2021, 12, 31),
+        ) {
+            assert!(ycal.decisions().iter().any(|d| d.holyday == ye.holyday
+                && d.outcome() == Outcome::Kept
+                && d.date() == ye.date()));
+        }
+    }
+    #[test]
+    fn test_transfer_schemes() {
+        /* 2021: Easter is 4 April, 14 March, 15, 22 and 29 August are Sundays */
+        let mut cal = calendar_of(&[
+            ("easter", calendar::HolydayClass::Principal, DateCal::Easter),
+            (
+                "lent festival",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 3, day: 14 },
+            ),
+            (
+                "festival",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 8, day: 22 },
+            ),
+            (
+                "transfiguration",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 8, day: 15 },
+            ),
+            (
+                "easter festival",
+                calendar::HolydayClass::Festival,
+                DateCal::Fixed { month: 4, day: 6 },
+            ),
+            (
+                "commemoration",
+                calendar::HolydayClass::Commemoration,
+                DateCal::Fixed { month: 8, day: 29 },
+            ),
+            (
+                "easter commemoration",
+                calendar::HolydayClass::Commemoration,
+                DateCal::Fixed { month: 4, day: 7 },
+            ),
+        ]);
+        let date = |m, d| Some(NaiveDate::from_ymd(2021, m, d));
+        for (province, expected) in [
+            (
+                calendar::Province::ChurchOfEngland,
+                [
+                    date(3, 15),
+                    date(8, 22),
+                    date(8, 15),
+                    date(4, 6),
+                    date(8, 29),
+                    None,
+                ],
+            ),
+            (
+                calendar::Province::ECUSA,
+                [
+                    date(3, 15),
+                    date(8, 23),
+                    date(8, 15),
+                    date(4, 12),
+                    None,
+                    None,
+                ],
+            ),
+            (
+                calendar::Province::BCP,
+                [
+                    date(3, 14),
+                    date(8, 22),
+                    date(8, 15),
+                    date(4, 6),
+                    date(8, 29),
+                    date(4, 7),
+                ],
+            ),
+            (
+                calendar::Province::Australia,
+                [
+                    date(3, 15),
+                    date(8, 22),
+                    date(8, 15),
+                    date(4, 6),
+                    None,
+                    None,
+                ],
+            ),
+        ]
+        .iter()
+        {
+            cal.province = *province;
+            let ycal =
+                YearCalendar::from_calendar(&cal, 2021, &YearCalendarOptions::default(), false)
+                    .unwrap();
+            for (tag, expected) in [
+                "lent festival",
+                "festival",
+                "transfiguration",
+                "easter festival",
+                "commemoration",
+                "easter commemoration",
+            ]
+            .iter()
+            .zip(expected.iter())
+            {
+                let dates: Vec<NaiveDate> =
+                    ycal.find_by_tag(tag).iter().map(|ye| ye.date()).collect();
+                assert_eq!(
+                    expected.iter().cloned().collect::<Vec<_>>(),
+                    dates,
+                    "{} in {:?}",
+                    tag,
+                    province
+                );
+                assert_eq!(
+                    expected.is_none(),
+                    !ycal.dropped_by_tag(tag).is_empty(),
+                    "{} dropped in {:?}",
+                    tag,
+                    province
+                );
+            }
+        }
+        /* the calendars of the provinces: in 2038 St Philip and St
+        James falls on the Saturday of Easter Week, in 2020 St Alphege
+        falls on the 2nd Sunday of Easter, in 2024 the Annunciation
+        falls on the Monday of Holy Week and in 2025 St Thomas falls on
+        the 4th Sunday of Advent */
+        for (province, tag, year, expected) in [
+            (
+                "cofe",
+                "philip james",
+                2038,
+                Some(NaiveDate::from_ymd(2038, 5, 1)),
+            ),
+            (
+                "ecusa",
+                "philip james",
+                2038,
+                Some(NaiveDate::from_ymd(2038, 5, 3)),
+            ),
+            (
+                "aca",
+                "philip james",
+                2038,
+                Some(NaiveDate::from_ymd(2038, 5, 1)),
+            ),
+            (
+                "bcp",
+                "philip james",
+                2038,
+                Some(NaiveDate::from_ymd(2038, 5, 1)),
+            ),
+            ("cofe", "alphege", 2020, None),
+            ("ecusa", "alphege", 2020, None),
+            (
+                "bcp",
+                "alphege",
+                2020,
+                Some(NaiveDate::from_ymd(2020, 4, 19)),
+            ),
+            (
+                "cofe",
+                "annunciation",
+                2024,
+                Some(NaiveDate::from_ymd(2024, 4, 8)),
+            ),
+            (
+                "bcp",
+                "annunciation",
+                2024,
+                Some(NaiveDate::from_ymd(2024, 3, 25)),
+            ),
+            (
+                "bcp",
+                "thomas",
+                2025,
+                Some(NaiveDate::from_ymd(2025, 12, 21)),
+            ),
+        ]
+        .iter()
+        {
+            let cal = calendar::Calendar::read(
+                std::fs::File::open(format!("data/final/{}.data", province)).unwrap(),
+            )
+            .unwrap();
+            let ycal =
+                YearCalendar::from_calendar(&cal, *year, &YearCalendarOptions::default(), false)
+                    .unwrap();
+            let dates: Vec<NaiveDate> = ycal.find_by_tag(tag).iter().map(|ye| ye.date()).collect();
+            assert_eq!(
+                expected.iter().cloned().collect::<Vec<_>>(),
+                dates,
+                "{} in {} {}",
+                tag,
+                province,
+                year
+            );
+        }
+    }
+    /** An holy day with the given tag (also used as the title), class
+    and date, and the defaults for everything else */
+    fn holyday(tag: &str, class: calendar::HolydayClass, date_cal: DateCal) -> Holyday {
+        Holyday {
+            title: tag.to_string(),
+            class,
+            tag: tag.to_string(),
+            date_cal,
+            ..Holyday::default()
+        }
+    }
+    /** A calendar of the given holy days */
+    fn calendar_from(holydays: impl IntoIterator<Item = Holyday>) -> calendar::Calendar {
+        let mut cal = calendar::Calendar::new();
+        for holyday in holydays {
+            cal.add(&holyday);
+        }
+        cal
+    }
+    /** A calendar of holy days given by tag, class and date; see
+    [holyday] */
+    fn calendar_of(holydays: &[(&str, calendar::HolydayClass, DateCal)]) -> calendar::Calendar {
+        calendar_from(
+            holydays
+                .iter()
+                .map(|(tag, class, date_cal)| holyday(tag, *class, date_cal.clone())),
+        )
+    }
     fn test_year(year_ad: i32, tests: &Vec<(DateCal, TransferType, NaiveDate)>) {
         let day_holydays: Vec<YearHolyday> = vec![];
         let year = Year::new(year_ad);
@@ -891,8 +5103,15 @@ mod tests {
                 transfer: t.clone(),
                 ..Holyday::default()
             };
-            let mut ye = YearHolyday::from_holyday(&calendar::HolydayRef::new(holyday), &year);
-            let er = YearCalendar::fix_holyday_date_is_ok(&day_holydays, &mut ye, &year);
+            let mut ye =
+                YearHolyday::from_holyday(&calendar::HolydayRef::new(holyday), &year).unwrap();
+            let er = YearCalendar::fix_holyday_date_is_ok(
+                &day_holydays,
+                &mut ye,
+                &year,
+                calendar::TransferScheme::CommonWorship,
+            )
+            .unwrap();
             assert_eq!(DropStatus::Keep, er);
             assert_eq!(
                 *ed,